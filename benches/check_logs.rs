@@ -0,0 +1,64 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use mental_instability_bot::{
+    log_checking::{
+        checks::check_checks, declarative::load_declarative_checks,
+        environment::get_environment_info, incompatibilities::load_incompatibilities,
+    },
+    log_upload::MapStatus,
+};
+
+// Same as the maximum log size the bot accepts
+const LOG_SIZE: usize = 8_000_000;
+
+const HEADER: &str = "\
+[12:34:50] [main/INFO]: Loading Minecraft 1.20.1 with Fabric Loader 0.15.11
+[12:34:50] [main/INFO]: Loading 4 mods:
+\t- fabric-api 0.92.2+1.20.1
+\t- fabricloader 0.15.11
+\t- java 17
+\t- minecraft 1.20.1
+";
+
+const FILLER: &str = "\
+[12:34:56] [Render thread/INFO]: [STDOUT]: Some mod printing way too much to the log
+[12:34:56] [Server thread/WARN]: Can't keep up! Is the server overloaded? Running 2034ms or 40 ticks behind
+[12:34:57] [Worker-Main-3/ERROR]: Couldn't load texture minecraft:textures/block/missing.png
+";
+
+const CRASH: &str = "\
+[12:35:01] [Render thread/ERROR]: Unreported exception thrown!
+java.lang.NoSuchFieldError: field_1234
+\tat net.minecraft.class_310.method_1523(class_310.java:1234) ~[client-intermediary.jar:?]
+";
+
+fn build_log() -> String {
+    let mut log = String::with_capacity(LOG_SIZE + HEADER.len() + CRASH.len());
+    log.push_str(HEADER);
+    while log.len() < LOG_SIZE {
+        log.push_str(FILLER);
+    }
+    log.push_str(CRASH);
+    log
+}
+
+fn scan(c: &mut Criterion) {
+    let log = build_log();
+    let declarative_checks = load_declarative_checks();
+    let incompatibilities = load_incompatibilities().expect("loading incompatibilities");
+    let map_status = MapStatus::NotRequired;
+
+    let mut group = c.benchmark_group("scan");
+    group.sample_size(10);
+    group.bench_function("check 8 MB log", |b| {
+        b.iter(|| {
+            let ctx = get_environment_info(black_box(&log), &map_status);
+            check_checks(&log, &ctx, &declarative_checks, &incompatibilities)
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, scan);
+criterion_main!(benches);
@@ -59,6 +59,10 @@
                   "-server.txt"
                   "message.txt"
                   ".log.gz"
+                  ".zip"
+                  ".tar.gz"
+                  ".tgz"
+                  ".tar"
               ];
             };
           };
@@ -103,6 +107,7 @@
             User = cfg.user;
             Group = cfg.group;
             WorkingDirectory = cfg.package;
+            CacheDirectory = "mental-instability-bot";
             ExecStart = "${cfg.package}/bin/mental-instability-bot";
             Restart = "always";
           };
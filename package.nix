@@ -12,6 +12,12 @@
   postInstall = ''
     cp -r log_checks $out/log_checks
     cp -r tags $out/tags
+    cp display.toml $out/display.toml
+    cp incompatibilities.toml $out/incompatibilities.toml
+    # Made by scripts/update-version-manifest.sh, the bot downloads it itself without it
+    if [ -f minecraft_versions.json ]; then
+      cp minecraft_versions.json $out/minecraft_versions.json
+    fi
   '';
 
   buildInputs = [
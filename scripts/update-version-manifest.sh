@@ -0,0 +1,10 @@
+#!/usr/bin/env sh
+# Downloads Mojang's version manifest to `minecraft_versions.json`, which the bot falls back on
+# when Mojang can't be reached and no manifest was cached by an earlier run. Commit the file as
+# it was downloaded, without editing it.
+set -eu
+
+cd "$(dirname "$0")/.."
+curl --fail --silent --show-error --location \
+    --output minecraft_versions.json \
+    https://piston-meta.mojang.com/mc/game/version_manifest_v2.json
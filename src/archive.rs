@@ -0,0 +1,401 @@
+use std::{
+    io::{self, Cursor, Read},
+    mem,
+};
+
+use anyhow::{anyhow, Result};
+use flate2::read::GzDecoder;
+use zip::ZipArchive;
+
+use crate::constants::{ARCHIVE_ENTRY_LIMIT, ARCHIVE_LOG_LIMIT, ARCHIVE_SIZE_LIMIT, MAX_LOG_SIZE};
+
+pub(crate) enum ArchiveKind {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+impl ArchiveKind {
+    pub(crate) fn from_file_name(name: &str) -> Option<ArchiveKind> {
+        let name = name.to_ascii_lowercase();
+        if name.ends_with(".zip") {
+            Some(ArchiveKind::Zip)
+        } else if name.ends_with(".tar") {
+            Some(ArchiveKind::Tar)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(ArchiveKind::TarGz)
+        } else {
+            None
+        }
+    }
+}
+
+/// A log found inside an archive.
+pub(crate) struct ArchivedLog {
+    /// Path of the log within the archive.
+    pub path: String,
+    pub data: Vec<u8>,
+}
+
+impl ArchivedLog {
+    pub(crate) fn file_name(&self) -> &str {
+        self.path.rsplit('/').next().unwrap_or(&self.path)
+    }
+}
+
+/// How interesting a file in an archive is, lowest first. `None` for files that aren't logs.
+fn log_rank(path: &str) -> Option<u8> {
+    let (dir, name) = path.rsplit_once('/').unwrap_or(("", path));
+    match name {
+        "latest.log" => Some(0),
+        "debug.log" => Some(1),
+        _ if name.ends_with(".txt") && dir.rsplit('/').next() == Some("crash-reports") => Some(2),
+        _ => None,
+    }
+}
+
+/// Unpacks the logs from an archive in memory, at most [`ARCHIVE_LOG_LIMIT`] of them with the
+/// newest crash reports first.
+pub(crate) fn extract_logs(kind: ArchiveKind, data: &[u8]) -> Result<Vec<ArchivedLog>> {
+    let mut logs = match kind {
+        ArchiveKind::Zip => read_zip(data)?,
+        ArchiveKind::Tar => read_tar(data)?,
+        ArchiveKind::TarGz => read_tar(GzDecoder::new(data))?,
+    };
+
+    // Crash reports are named after the time they were made
+    logs.sort_by(|a, b| {
+        log_rank(&a.path)
+            .cmp(&log_rank(&b.path))
+            .then_with(|| b.file_name().cmp(a.file_name()))
+    });
+    logs.truncate(ARCHIVE_LOG_LIMIT);
+    Ok(logs)
+}
+
+fn too_large() -> anyhow::Error {
+    anyhow!("Archive is larger than the maximum allowed size")
+}
+
+/// The error for when a tar archive ends in the middle of a file.
+fn cut_off<R>(reader: &io::Take<R>) -> anyhow::Error {
+    if reader.limit() == 0 {
+        too_large()
+    } else {
+        anyhow!("Archive is incomplete")
+    }
+}
+
+fn read_zip(data: &[u8]) -> Result<Vec<ArchivedLog>> {
+    let mut archive = ZipArchive::new(Cursor::new(data))?;
+    if archive.len() > ARCHIVE_ENTRY_LIMIT {
+        return Err(anyhow!("Archive has more than {ARCHIVE_ENTRY_LIMIT} files"));
+    }
+
+    let mut logs = vec![];
+    let mut total = 0;
+    for i in 0..archive.len() {
+        let file = archive.by_index(i)?;
+        let path = file.name().replace('\\', "/");
+        if !file.is_file() || log_rank(&path).is_none() || file.size() > MAX_LOG_SIZE.into() {
+            continue;
+        }
+
+        // The sizes in the archive can't be trusted, so the limits are checked while reading
+        let mut contents = vec![];
+        file.take(u64::from(MAX_LOG_SIZE) + 1).read_to_end(&mut contents)?;
+        total += contents.len() as u64;
+        if contents.len() > MAX_LOG_SIZE as usize || total > ARCHIVE_SIZE_LIMIT {
+            return Err(too_large());
+        }
+        logs.push(ArchivedLog {
+            path,
+            data: contents,
+        });
+    }
+    Ok(logs)
+}
+
+/// Parses a number from a tar header, which are octal and padded with NULs or spaces.
+fn tar_number(field: &[u8]) -> Result<u64> {
+    let text = std::str::from_utf8(field)?.trim_matches(['\0', ' ']);
+    if text.is_empty() {
+        return Ok(0);
+    }
+    Ok(u64::from_str_radix(text, 8)?)
+}
+
+fn tar_string(field: &[u8]) -> String {
+    let end = field.iter().position(|b| *b == 0).unwrap_or(field.len());
+    String::from_utf8_lossy(&field[..end]).into_owned()
+}
+
+/// What a GNU long name or PAX extended header says about the entry after it.
+#[derive(Default)]
+struct ExtendedHeader {
+    path: Option<String>,
+    size: Option<u64>,
+}
+
+impl ExtendedHeader {
+    /// Reads the records of a PAX header, which look like `<length> <key>=<value>\n` with the
+    /// length counting the whole record.
+    fn read_pax(&mut self, mut data: &[u8]) -> Result<()> {
+        let malformed = || anyhow!("Archive has a malformed PAX header");
+        while !data.is_empty() {
+            let space = data.iter().position(|b| *b == b' ').ok_or_else(malformed)?;
+            let length: usize = std::str::from_utf8(&data[..space])?.parse()?;
+            let record = data
+                .get(space + 1..length)
+                .and_then(|record| record.strip_suffix(b"\n"))
+                .ok_or_else(malformed)?;
+            if let Some(path) = record.strip_prefix(b"path=") {
+                self.path = Some(String::from_utf8_lossy(path).into_owned());
+            } else if let Some(size) = record.strip_prefix(b"size=") {
+                self.size = Some(std::str::from_utf8(size)?.parse()?);
+            }
+            data = &data[length..];
+        }
+        Ok(())
+    }
+}
+
+/// Reads an entry's data, and skips the padding after it.
+fn read_tar_entry<R: Read>(reader: &mut io::Take<R>, size: u64) -> Result<Vec<u8>> {
+    let mut data = vec![];
+    reader.by_ref().take(size).read_to_end(&mut data)?;
+    if (data.len() as u64) < size {
+        return Err(cut_off(reader));
+    }
+    skip_tar_entry(reader, size.next_multiple_of(512) - size)?;
+    Ok(data)
+}
+
+fn skip_tar_entry<R: Read>(reader: &mut io::Take<R>, size: u64) -> Result<()> {
+    io::copy(&mut reader.by_ref().take(size), &mut io::sink())?;
+    Ok(())
+}
+
+/// Reads ustar archives, with GNU long names and PAX headers. Only the logs are kept, everything
+/// else is skipped over without being stored.
+fn read_tar(reader: impl Read) -> Result<Vec<ArchivedLog>> {
+    let mut reader = reader.take(ARCHIVE_SIZE_LIMIT);
+    let mut logs = vec![];
+    let mut extended = ExtendedHeader::default();
+
+    for _ in 0..ARCHIVE_ENTRY_LIMIT {
+        let mut header = [0; 512];
+        match reader.read_exact(&mut header) {
+            Ok(()) => {}
+            // Some tools leave off the empty blocks at the end
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof && reader.limit() > 0 => {
+                return Ok(logs);
+            }
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Err(too_large()),
+            Err(err) => return Err(err.into()),
+        }
+        if header.iter().all(|b| *b == 0) {
+            return Ok(logs);
+        }
+
+        let kind = header[156];
+        let size = tar_number(&header[124..136])?;
+        if matches!(kind, b'L' | b'x' | b'g') {
+            // Global PAX headers only hold defaults like the owner
+            if kind == b'g' || size > MAX_LOG_SIZE.into() {
+                skip_tar_entry(&mut reader, size.next_multiple_of(512))?;
+            } else if kind == b'L' {
+                extended.path = Some(tar_string(&read_tar_entry(&mut reader, size)?));
+            } else {
+                extended.read_pax(&read_tar_entry(&mut reader, size)?)?;
+            }
+            continue;
+        }
+
+        let ExtendedHeader {
+            path,
+            size: real_size,
+        } = mem::take(&mut extended);
+        let size = real_size.unwrap_or(size);
+        let name = path.unwrap_or_else(|| {
+            let name = tar_string(&header[..100]);
+            match tar_string(&header[345..500]) {
+                prefix if &header[257..262] == b"ustar" && !prefix.is_empty() => {
+                    format!("{prefix}/{name}")
+                }
+                _ => name,
+            }
+        });
+
+        if matches!(kind, b'0' | 0) && log_rank(&name).is_some() && size <= MAX_LOG_SIZE.into() {
+            let data = read_tar_entry(&mut reader, size)?;
+            logs.push(ArchivedLog { path: name, data });
+        } else {
+            skip_tar_entry(&mut reader, size.next_multiple_of(512))?;
+        }
+    }
+
+    Err(anyhow!("Archive has more than {ARCHIVE_ENTRY_LIMIT} files"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::{write::GzEncoder, Compression};
+
+    use super::*;
+
+    fn header(name: &str, kind: u8, size: usize) -> [u8; 512] {
+        let mut header = [0; 512];
+        header[..name.len()].copy_from_slice(name.as_bytes());
+        header[124..135].copy_from_slice(format!("{size:011o}").as_bytes());
+        header[156] = kind;
+        header[257..263].copy_from_slice(b"ustar\0");
+        header
+    }
+
+    fn entry(header: [u8; 512], data: &[u8]) -> Vec<u8> {
+        let mut entry = header.to_vec();
+        entry.extend_from_slice(data);
+        entry.resize(entry.len().next_multiple_of(512), 0);
+        entry
+    }
+
+    fn file(name: &str, data: &[u8]) -> Vec<u8> {
+        entry(header(name, b'0', data.len()), data)
+    }
+
+    fn archive(entries: &[Vec<u8>]) -> Vec<u8> {
+        let mut archive = entries.concat();
+        archive.extend_from_slice(&[0; 1024]);
+        archive
+    }
+
+    fn paths(logs: &[ArchivedLog]) -> Vec<&str> {
+        logs.iter().map(|log| log.path.as_str()).collect()
+    }
+
+    #[test]
+    fn ustar() {
+        let mut prefixed = header("crash-2024-05-01_12.00.00-client.txt", b'0', 5);
+        prefixed[345..365].copy_from_slice(b".minecraft/crash-rep");
+        prefixed[365..372].copy_from_slice(b"orts\0\0\0");
+        let data = archive(&[
+            file(".minecraft/options.txt", b"fov:70"),
+            file(".minecraft/logs/latest.log", b"latest"),
+            entry(prefixed, b"crash"),
+        ]);
+
+        let logs = extract_logs(ArchiveKind::Tar, &data).unwrap();
+        assert_eq!(
+            paths(&logs),
+            [
+                ".minecraft/logs/latest.log",
+                ".minecraft/crash-reports/crash-2024-05-01_12.00.00-client.txt"
+            ]
+        );
+        assert_eq!(logs[0].data, b"latest");
+        assert_eq!(logs[1].data, b"crash");
+    }
+
+    #[test]
+    fn gnu_long_name() {
+        let name = format!("{}/logs/latest.log", "instances/a".repeat(12));
+        let data = archive(&[
+            entry(
+                header("././@LongLink", b'L', name.len() + 1),
+                format!("{name}\0").as_bytes(),
+            ),
+            file(&name[..100], b"latest"),
+            file("logs/debug.log", b"debug"),
+        ]);
+
+        let logs = extract_logs(ArchiveKind::Tar, &data).unwrap();
+        assert_eq!(paths(&logs), [name.as_str(), "logs/debug.log"]);
+    }
+
+    #[test]
+    fn pax_headers() {
+        let path = format!("{}/logs/latest.log", "instances/b".repeat(12));
+        // The length counts itself, which is 3 digits here
+        let record = format!("path={path}\n");
+        let records = format!("{} {record}6 a=b\n10 size=6\n", record.len() + 4);
+        let data = archive(&[
+            entry(header("pax_global_header", b'g', 12), b"6 a=b\n6 c=d\n"),
+            entry(
+                header("PaxHeaders/latest.log", b'x', records.len()),
+                records.as_bytes(),
+            ),
+            // The real size is only in the PAX header
+            entry(header("latest.lo", b'0', 0), b"latest"),
+            file("logs/debug.log", b"debug"),
+        ]);
+
+        let logs = extract_logs(ArchiveKind::Tar, &data).unwrap();
+        assert_eq!(paths(&logs), [path.as_str(), "logs/debug.log"]);
+        assert_eq!(logs[0].data, b"latest");
+
+        let malformed = archive(&[entry(header("PaxHeaders/x", b'x', 8), b"99 a=b\n\n")]);
+        assert!(extract_logs(ArchiveKind::Tar, &malformed).is_err());
+    }
+
+    #[test]
+    fn compressed() {
+        let mut encoder = GzEncoder::new(vec![], Compression::default());
+        encoder
+            .write_all(&archive(&[file("logs/latest.log", b"latest")]))
+            .unwrap();
+        let data = encoder.finish().unwrap();
+
+        assert_eq!(
+            paths(&extract_logs(ArchiveKind::TarGz, &data).unwrap()),
+            ["logs/latest.log"]
+        );
+    }
+
+    #[test]
+    fn truncated() {
+        let mut data = file("logs/latest.log", &[b'a'; 2000]);
+        data.truncate(1024);
+        let err = extract_logs(ArchiveKind::Tar, &data).err().unwrap();
+        assert_eq!(err.to_string(), "Archive is incomplete");
+
+        // Without the blocks at the end is fine
+        let data = file("logs/latest.log", b"latest");
+        assert_eq!(
+            paths(&extract_logs(ArchiveKind::Tar, &data).unwrap()),
+            ["logs/latest.log"]
+        );
+    }
+
+    #[test]
+    fn over_limits() {
+        // Logs claiming to be too big are skipped without being read
+        let data = archive(&[entry(
+            header("logs/latest.log", b'0', MAX_LOG_SIZE as usize + 1),
+            b"",
+        )]);
+        assert!(extract_logs(ArchiveKind::Tar, &data).unwrap().is_empty());
+
+        let entries = vec![file("empty.txt", b""); ARCHIVE_ENTRY_LIMIT + 1];
+        let err = extract_logs(ArchiveKind::Tar, &archive(&entries))
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            format!("Archive has more than {ARCHIVE_ENTRY_LIMIT} files")
+        );
+
+        // Compressed, as an archive that's too big uncompressed is the one to watch out for
+        let mut encoder = GzEncoder::new(vec![], Compression::fast());
+        let size = ARCHIVE_SIZE_LIMIT as usize;
+        encoder.write_all(&header("world.bin", b'0', size)).unwrap();
+        for _ in 0..size / 1_000_000 + 1 {
+            encoder.write_all(&[0; 1_000_000]).unwrap();
+        }
+        let data = encoder.finish().unwrap();
+        let err = extract_logs(ArchiveKind::TarGz, &data).err().unwrap();
+        assert_eq!(err.to_string(), too_large().to_string());
+    }
+}
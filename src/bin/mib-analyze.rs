@@ -0,0 +1,305 @@
+//! Runs the log checks over a local file or stdin, without needing Discord or network access.
+//!
+//! Usage: `mib-analyze [--json] [--mappings <mappings.tiny>] [--diff <old file>] [<file>|-]`
+//!
+//! With `--diff`, prints what changed from the old log to the new one instead.
+
+use std::{
+    env,
+    fs::{self, File},
+    io::{self, Read},
+    process::ExitCode,
+    time::Instant,
+};
+
+use anyhow::{anyhow, Result};
+use mental_instability_bot::{
+    log_checking::{
+        checks::{check_checks, CheckReport},
+        declarative::{load_declarative_checks, DeclarativeCheck},
+        diff::LogDiff,
+        environment::{get_environment_info, read_mc_version, EnvironmentContext},
+        incompatibilities::{load_incompatibilities, Incompatibility},
+        minecraft::VersionManifestCache,
+        records::Level,
+    },
+    log_upload::MapStatus,
+    mappings::download::parse_mappings,
+};
+use serde_json::json;
+
+struct Args {
+    json: bool,
+    mappings: Option<String>,
+    diff: Option<String>,
+    input: Option<String>,
+}
+
+fn parse_args() -> Result<Args> {
+    let mut args = Args {
+        json: false,
+        mappings: None,
+        diff: None,
+        input: None,
+    };
+
+    let mut iter = env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--json" => args.json = true,
+            "--mappings" => {
+                args.mappings = Some(
+                    iter.next()
+                        .ok_or(anyhow!("--mappings requires a path to a tiny file"))?,
+                )
+            }
+            "--diff" => {
+                args.diff = Some(
+                    iter.next()
+                        .ok_or(anyhow!("--diff requires a path to the old log"))?,
+                )
+            }
+            "-h" | "--help" => {
+                return Err(anyhow!(
+                    "Usage: mib-analyze [--json] [--mappings <mappings.tiny>] [--diff <old file>] [<file>|-]"
+                ))
+            }
+            _ if args.input.is_none() => args.input = Some(arg),
+            _ => return Err(anyhow!("Unexpected argument: {arg}")),
+        }
+    }
+
+    Ok(args)
+}
+
+fn read_input(input: Option<&str>) -> Result<String> {
+    let mut data = vec![];
+    match input {
+        None | Some("-") => {
+            io::stdin().read_to_end(&mut data)?;
+        }
+        Some(path) => {
+            data = fs::read(path)?;
+        }
+    }
+    Ok(String::from_utf8_lossy(&data).into_owned())
+}
+
+fn count_level(ctx: &EnvironmentContext, level: Level) -> usize {
+    ctx.records.iter().filter(|r| r.level == level).count()
+}
+
+fn print_text(ctx: &EnvironmentContext, checks: &[CheckReport]) {
+    if let Some(launcher) = &ctx.launcher {
+        println!("Launcher:  {}", launcher.name());
+    }
+    if let Some(version) = &ctx.mc_version {
+        println!("Minecraft: {version}");
+    }
+    if let Some(version) = &ctx.java_version {
+        match &ctx.java_vendor {
+            Some(vendor) => println!("Java:      {version} ({vendor})"),
+            None => println!("Java:      {version}"),
+        }
+    }
+    if let Some(os) = &ctx.os {
+        match &ctx.arch {
+            Some(arch) => println!("System:    {os} ({arch})"),
+            None => println!("System:    {os}"),
+        }
+    }
+    if let Some(system) = &ctx.system {
+        if let Some(cpu) = system.cpu {
+            println!("CPU:       {cpu}");
+        }
+        if let Some(gpu) = &system.gpu {
+            println!("GPU:       {}", gpu.renderer);
+        }
+        if let Some(memory) = system.max_memory {
+            println!("Memory:    {memory} MiB");
+        }
+    }
+    if let Some(loader) = &ctx.loader {
+        match loader.version() {
+            Some(version) => println!("Loader:    {} {version}", loader.name()),
+            None => println!("Loader:    {}", loader.name()),
+        }
+    }
+    if let MapStatus::Mapped(took) = ctx.map_status {
+        println!("Remapped in {}ms", took.as_millis());
+    }
+    println!(
+        "Mods:      {} ({} bundled)",
+        ctx.discovered_mods.iter().filter(|m| !m.builtin).count(),
+        ctx.discovered_mods
+            .iter()
+            .filter(|m| !m.builtin && m.parent.is_some())
+            .count()
+    );
+    println!(
+        "Records:   {} ({} errors, {} warnings)",
+        ctx.records.len(),
+        count_level(ctx, Level::Error) + count_level(ctx, Level::Fatal),
+        count_level(ctx, Level::Warn)
+    );
+
+    for check in checks {
+        println!("\n[{:?}] {}\n{}", check.severity, check.title, check.description);
+    }
+}
+
+fn print_json(ctx: &EnvironmentContext, checks: &[CheckReport]) -> Result<()> {
+    let mut mods = ctx
+        .discovered_mods
+        .iter()
+        .map(|m| {
+            json!({
+                "id": m.id,
+                "version": m.version,
+                "parent": m.parent,
+                "builtin": m.builtin,
+            })
+        })
+        .collect::<Vec<_>>();
+    mods.sort_by(|a, b| a["id"].as_str().cmp(&b["id"].as_str()));
+
+    let output = json!({
+        "launcher": ctx.launcher.as_ref().map(|l| l.name()),
+        "minecraft": ctx.mc_version,
+        "java": ctx.java_version,
+        "java_vendor": ctx.java_vendor,
+        "os": ctx.os,
+        "arch": ctx.arch,
+        "cpu": ctx.system.as_ref().and_then(|s| s.cpu),
+        "gpu": ctx.system.as_ref().and_then(|s| s.gpu.as_ref()).map(|g| g.renderer),
+        "max_memory": ctx.system.as_ref().and_then(|s| s.max_memory),
+        "loader": ctx.loader.as_ref().map(|l| json!({
+            "name": l.name(),
+            "version": l.version(),
+        })),
+        "remapped": matches!(ctx.map_status, MapStatus::Mapped(_)),
+        "mods": mods,
+        "records": {
+            "total": ctx.records.len(),
+            "errors": count_level(ctx, Level::Error) + count_level(ctx, Level::Fatal),
+            "warnings": count_level(ctx, Level::Warn),
+        },
+        "checks": checks,
+    });
+    println!("{}", serde_json::to_string_pretty(&output)?);
+    Ok(())
+}
+
+fn print_diff(diff: &LogDiff) {
+    if diff.is_empty() {
+        println!("No differences");
+        return;
+    }
+    for (name, old, new) in &diff.environment {
+        println!("{name}: {old} -> {new}");
+    }
+    let lists = [
+        ("Added mods", &diff.added_mods),
+        ("Removed mods", &diff.removed_mods),
+    ];
+    for (name, mods) in lists {
+        if !mods.is_empty() {
+            println!("\n{name}:");
+            for (id, version) in mods {
+                println!("  {id} {version}");
+            }
+        }
+    }
+    if !diff.updated_mods.is_empty() {
+        println!("\nUpdated mods:");
+        for (id, old, new) in &diff.updated_mods {
+            println!("  {id} {old} -> {new}");
+        }
+    }
+    let lists = [
+        ("New issues", &diff.new_checks),
+        ("Resolved issues", &diff.resolved_checks),
+    ];
+    for (name, checks) in lists {
+        if !checks.is_empty() {
+            println!("\n{name}:");
+            for (severity, title) in checks {
+                println!("  [{severity:?}] {title}");
+            }
+        }
+    }
+}
+
+/// Remaps the log if mappings were given.
+fn remap(log: String, mappings: Option<&str>) -> Result<(String, MapStatus)> {
+    let Some(path) = mappings else {
+        return Ok((log, MapStatus::NotRequired));
+    };
+    if read_mc_version(&log).is_none() {
+        return Ok((log, MapStatus::InvalidMcVersion));
+    }
+    let start = Instant::now();
+    let mappings = parse_mappings(File::open(path)?)?;
+    let log = mappings.remap_log(&log);
+    Ok((log, MapStatus::Mapped(Instant::now() - start)))
+}
+
+fn analyze<'a>(
+    log: &'a str,
+    map_status: &'a MapStatus,
+    declarative_checks: &[DeclarativeCheck],
+    incompatibilities: &[Incompatibility],
+    version_manifest: &VersionManifestCache,
+) -> (EnvironmentContext<'a>, Vec<CheckReport>) {
+    let mut ctx = get_environment_info(log, map_status);
+    ctx.game_version = ctx
+        .mc_version
+        .as_deref()
+        .and_then(|v| version_manifest.game_version(v));
+    let checks = check_checks(log, &ctx, declarative_checks, incompatibilities);
+    (ctx, checks)
+}
+
+fn run() -> Result<()> {
+    let args = parse_args()?;
+    let (log, map_status) = remap(read_input(args.input.as_deref())?, args.mappings.as_deref())?;
+
+    let declarative_checks = load_declarative_checks();
+    let incompatibilities = load_incompatibilities()?;
+    // Whichever manifest is on disk, as refreshing it would need network access
+    let version_manifest = VersionManifestCache::create();
+    let (ctx, checks) = analyze(
+        &log,
+        &map_status,
+        &declarative_checks,
+        &incompatibilities,
+        &version_manifest,
+    );
+
+    if let Some(old_path) = &args.diff {
+        let (old_log, old_map_status) = remap(read_input(Some(old_path))?, args.mappings.as_deref())?;
+        let (old_ctx, old_checks) = analyze(
+            &old_log,
+            &old_map_status,
+            &declarative_checks,
+            &incompatibilities,
+            &version_manifest,
+        );
+        print_diff(&LogDiff::new((&old_ctx, &old_checks), (&ctx, &checks)));
+    } else if args.json {
+        print_json(&ctx, &checks)?;
+    } else {
+        print_text(&ctx, &checks);
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use crate::log_upload::check_for_log_diff;
+
+use super::{Context, Error};
+use poise::CreateReply;
+use serenity::all::{Attachment, Message};
+
+/// Compare two logs, to see what changed since the game last worked
+#[poise::command(
+    slash_command,
+    install_context = "Guild|User",
+    interaction_context = "Guild|BotDm|PrivateChannel"
+)]
+pub async fn logdiff(
+    ctx: Context<'_>,
+    #[description = "Link to the log from before the problem started"] before: Option<String>,
+    #[description = "The log from before the problem started"] before_file: Option<Attachment>,
+    #[description = "Link to the log with the problem"] after: Option<String>,
+    #[description = "The log with the problem"] after_file: Option<Attachment>,
+) -> Result<(), Error> {
+    let before_files = Vec::from_iter(before_file);
+    let after_files = Vec::from_iter(after_file);
+    compare(
+        ctx,
+        &[
+            (&before_files, before.as_deref().unwrap_or_default()),
+            (&after_files, after.as_deref().unwrap_or_default()),
+        ],
+    )
+    .await
+}
+
+#[poise::command(
+    context_menu_command = "Compare logs",
+    install_context = "Guild|User",
+    interaction_context = "Guild|BotDm|PrivateChannel"
+)]
+pub async fn logdiff_message(ctx: Context<'_>, msg: Message) -> Result<(), Error> {
+    compare(ctx, &[(&msg.attachments, &msg.content)]).await
+}
+
+async fn compare(ctx: Context<'_>, sources: &[(&[Attachment], &str)]) -> Result<(), Error> {
+    let reply = ctx.reply("Comparing logs...").await?;
+
+    match check_for_log_diff(ctx.serenity_context(), sources).await {
+        Ok(Some((embed, components))) => {
+            reply
+                .edit(
+                    ctx,
+                    CreateReply::default()
+                        .content("")
+                        .embed(embed)
+                        .components(components),
+                )
+                .await?;
+        }
+        Ok(None) => {
+            reply
+                .edit(
+                    ctx,
+                    CreateReply::default().content("Two logs are needed to compare."),
+                )
+                .await?;
+        }
+        Err(err) => {
+            reply
+                .edit(
+                    ctx,
+                    CreateReply::default().content(format!("Error, cannot compare logs: {err}")),
+                )
+                .await?;
+        }
+    };
+    Ok(())
+}
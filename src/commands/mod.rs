@@ -2,9 +2,11 @@ use crate::ConfigData;
 
 pub mod general;
 pub mod quote;
+pub mod reload;
 pub mod tags;
 pub mod version;
 pub mod check_logs;
+pub mod logdiff;
 pub mod modversion;
 pub mod update_deps;
 pub mod yarn;
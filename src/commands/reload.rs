@@ -0,0 +1,120 @@
+use std::fmt::Write;
+
+use poise::CreateReply;
+
+use crate::{
+    commands::tags::{load_tags, sync_tag_commands},
+    log_checking::{
+        declarative::read_declarative_checks, display::load_display_data,
+        incompatibilities::load_incompatibilities,
+    },
+    truncate, DisplayDataKey, IncompatibilitiesKey, LogCacheKey, LogChecksKey, TagsKey,
+};
+
+use super::{Context, Error};
+
+/// Reload tags, log checks, incompatibilities and display data from disk
+#[poise::command(
+    slash_command,
+    owners_only,
+    install_context = "Guild|User",
+    interaction_context = "Guild|BotDm|PrivateChannel"
+)]
+pub(crate) async fn reload(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let command_names = ctx
+        .framework()
+        .options()
+        .commands
+        .iter()
+        .map(|command| command.name.as_str())
+        .collect::<Vec<_>>();
+    let (mut tags, tag_errors) = load_tags(&command_names);
+    let (mut checks, check_errors) = read_declarative_checks();
+
+    let (old_tags, (incompatibilities, incompatibility_error), (display, display_error)) = {
+        let data = ctx.serenity_context().data.read().await;
+
+        // Keep the last working version of anything that broke, rather than dropping it
+        let old_tags = data.get::<TagsKey>().expect("No tags?");
+        for (name, _) in &tag_errors {
+            if let Some(tag) = old_tags.get(name) {
+                tags.insert(name.clone(), tag.clone());
+            }
+        }
+        let old_checks = data.get::<LogChecksKey>().expect("No log checks?");
+        for (name, _) in &check_errors {
+            if let Some(check) = old_checks.iter().find(|c| &c.name == name) {
+                checks.push(check.clone());
+            }
+        }
+        checks.sort_by(|a, b| a.name.cmp(&b.name));
+        let old_incompatibilities = data
+            .get::<IncompatibilitiesKey>()
+            .expect("No incompatibilities?");
+        let old_display = data.get::<DisplayDataKey>().expect("No display data?");
+
+        (
+            old_tags.clone(),
+            match load_incompatibilities() {
+                Ok(incompatibilities) => (incompatibilities, None),
+                Err(err) => (old_incompatibilities.clone(), Some(err)),
+            },
+            match load_display_data(old_display.custom_emoji) {
+                Ok(display) => (display, None),
+                Err(err) => (old_display.clone(), Some(err)),
+            },
+        )
+    };
+
+    let (added, removed) = sync_tag_commands(ctx.serenity_context(), &old_tags, &tags).await?;
+
+    let mut output = format!(
+        "Reloaded {} tags, {} log checks and {} incompatibilities.",
+        tags.len(),
+        checks.len(),
+        incompatibilities.len()
+    );
+    if added > 0 || removed > 0 {
+        write!(output, "\nRegistered {added} and removed {removed} tag commands.")?;
+    }
+    if !tag_errors.is_empty()
+        || !check_errors.is_empty()
+        || incompatibility_error.is_some()
+        || display_error.is_some()
+    {
+        write!(output, "\n\n**Failed to load, keeping the previous version:**")?;
+        for (name, err) in &tag_errors {
+            write!(output, "\n- Tag `{name}`: {}", truncate!(err.clone(), 256))?;
+        }
+        for (name, err) in &check_errors {
+            write!(output, "\n- Check `{name}`: {}", truncate!(err.clone(), 256))?;
+        }
+        if let Some(err) = &incompatibility_error {
+            write!(output, "\n- Incompatibilities: {}", truncate!(err.to_string(), 256))?;
+        }
+        if let Some(err) = &display_error {
+            write!(output, "\n- Display data: {}", truncate!(err.to_string(), 256))?;
+        }
+    }
+
+    {
+        let mut data = ctx.serenity_context().data.write().await;
+        data.insert::<TagsKey>(tags);
+        data.insert::<LogChecksKey>(checks);
+        data.insert::<IncompatibilitiesKey>(incompatibilities);
+        data.insert::<DisplayDataKey>(display);
+        data.get::<LogCacheKey>()
+            .expect("No log cache?")
+            .clear_reports();
+    }
+
+    ctx.send(
+        CreateReply::default()
+            .content(truncate!(output, 2000))
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(())
+}
@@ -1,19 +1,24 @@
+use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
 
-use poise::CreateReply;
 use serde::Deserialize;
+use serenity::all::Command;
+use serenity::all::CommandInteraction;
+use serenity::all::CreateCommand;
+use serenity::all::CreateInteractionResponse;
+use serenity::all::CreateInteractionResponseMessage;
+use serenity::all::CreateMessage;
 use serenity::all::InstallationContext;
 use serenity::all::InteractionContext;
+use serenity::all::Message;
 use serenity::builder::CreateEmbed;
 
-use crate::ConfigData;
+use crate::TagsKey;
 
-use super::Context;
 use super::Error;
 
-#[derive(Deserialize)]
-struct Tag {
+#[derive(Deserialize, Clone)]
+pub struct Tag {
     title: String,
     description: Option<String>,
     thumbnail: Option<String>,
@@ -21,79 +26,149 @@ struct Tag {
     color: u32,
 }
 
-pub fn load_tag_commands() -> Vec<poise::Command<ConfigData, Error>> {
-    let files = fs::read_dir("./tags/").expect("reading tags directory");
+impl Tag {
+    fn embed(&self) -> CreateEmbed {
+        let mut embed = CreateEmbed::new().title(&self.title).color(self.color);
+        if let Some(description) = &self.description {
+            embed = embed.description(description);
+        }
+        if let Some(thumbnail) = &self.thumbnail {
+            embed = embed.thumbnail(thumbnail);
+        }
+        if let Some(image) = &self.image {
+            embed = embed.image(image);
+        }
+        embed
+    }
+}
 
-    let mut result = vec![];
+/// Reads every tag in `./tags/`, skipping the ones that fail to load and returning their
+/// errors along with the tag's name. Tags can't share a name with one of the `commands`, as
+/// both would answer it.
+pub(crate) fn load_tags(commands: &[&str]) -> (HashMap<String, Tag>, Vec<(String, String)>) {
+    let mut tags = HashMap::new();
+    let mut errors = vec![];
 
-    for file in files {
-        let file = file.expect("locating tag");
-        let path = file.path();
-        let file_name = file.file_name().into_string().expect("reading filename");
+    let files = match fs::read_dir("./tags/") {
+        Ok(files) => files,
+        Err(err) => return (tags, vec![("tags".to_string(), err.to_string())]),
+    };
 
-        if Path::new(&file_name)
+    for file in files.filter_map(Result::ok) {
+        let path = file.path();
+        if !path
             .extension()
             .map_or(false, |ext| ext.eq_ignore_ascii_case("json5"))
         {
-            let tag_name = file_name.strip_suffix(".json5").unwrap();
-            let tag = json5::from_str::<Tag>(&fs::read_to_string(path).expect("reading tag"))
-                .expect("parsing tag");
+            continue;
+        }
+        let Some(tag_name) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        if commands.contains(&tag_name) {
+            errors.push((
+                tag_name.to_string(),
+                format!("There is already a /{tag_name} command"),
+            ));
+            continue;
+        }
 
-            result.push(tag_command(tag_name, tag));
+        let loaded = fs::read_to_string(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|s| Ok(json5::from_str::<Tag>(&s)?));
+        match loaded {
+            Ok(tag) => {
+                tags.insert(tag_name.to_string(), tag);
+            }
+            Err(err) => errors.push((tag_name.to_string(), err.to_string())),
         }
     }
 
-    result
+    (tags, errors)
 }
 
-fn tag_command(tag_name: &str, tag: Tag) -> poise::Command<ConfigData, Error> {
-    async fn inner(ctx: Context<'_>) -> Result<(), Error> {
-        let data = ctx.command().custom_data.as_ref();
-        let tag = data
-            .downcast_ref::<Tag>()
-            .expect("Tag command broke, what??");
+/// The slash command a tag is registered as. Tags are dispatched by the event handler rather
+/// than poise, so they can be added and removed while the bot is running.
+pub(crate) fn tag_command(tag_name: &str) -> CreateCommand {
+    CreateCommand::new(tag_name)
+        .description(format!("Displays the {tag_name} tag"))
+        .integration_types(vec![InstallationContext::Guild, InstallationContext::User])
+        .contexts(vec![
+            InteractionContext::Guild,
+            InteractionContext::BotDm,
+            InteractionContext::PrivateChannel,
+        ])
+}
 
-        let mut embed = CreateEmbed::new().title(&tag.title).color(tag.color);
-        if let Some(description) = &tag.description {
-            embed = embed.description(description);
-        }
-        if let Some(thumbnail) = &tag.thumbnail {
-            embed = embed.thumbnail(thumbnail);
+/// Responds to the interaction if it's for a tag, returning whether it was.
+pub(crate) async fn handle_tag_interaction(
+    ctx: &serenity::all::Context,
+    command: &CommandInteraction,
+) -> Result<bool, Error> {
+    let embed = {
+        let data = ctx.data.read().await;
+        let tags = data.get::<TagsKey>().expect("No tags?");
+        match tags.get(&command.data.name) {
+            Some(tag) => tag.embed(),
+            None => return Ok(false),
         }
-        if let Some(image) = &tag.image {
-            embed = embed.image(image);
+    };
+
+    let message = CreateInteractionResponseMessage::new().embed(embed);
+    command
+        .create_response(ctx, CreateInteractionResponse::Message(message))
+        .await?;
+    Ok(true)
+}
+
+/// Responds to `@bot <tag>` messages, which poise passes on as unknown commands. Returns
+/// whether the message was for a tag.
+pub(crate) async fn handle_tag_message(
+    ctx: &serenity::all::Context,
+    message: &Message,
+    content: &str,
+) -> Result<bool, Error> {
+    let Some(name) = content.split_whitespace().next() else {
+        return Ok(false);
+    };
+    let embed = {
+        let data = ctx.data.read().await;
+        let tags = data.get::<TagsKey>().expect("No tags?");
+        match tags.get(name) {
+            Some(tag) => tag.embed(),
+            None => return Ok(false),
         }
-        let message = CreateReply::default().embed(embed);
+    };
 
-        ctx.send(message).await?;
-        Ok(())
+    message
+        .channel_id
+        .send_message(ctx, CreateMessage::new().embed(embed))
+        .await?;
+    Ok(true)
+}
+
+/// Creates commands for new tags and deletes the ones of removed tags, leaving the rest alone,
+/// as a tag's content isn't part of its registration. Returns how many were added and removed.
+pub(crate) async fn sync_tag_commands(
+    ctx: &serenity::all::Context,
+    old: &HashMap<String, Tag>,
+    new: &HashMap<String, Tag>,
+) -> Result<(usize, usize), Error> {
+    let mut added = 0;
+    for name in new.keys().filter(|name| !old.contains_key(*name)) {
+        Command::create_global_command(ctx, tag_command(name)).await?;
+        added += 1;
     }
 
-    poise::Command {
-        name: tag_name.to_string(),
-        description: Some(format!("Displays the {tag_name} tag")),
-        prefix_action: Some(|ctx| {
-            Box::pin(async move {
-                inner(ctx.into())
-                    .await
-                    .map_err(|error| poise::FrameworkError::new_command(ctx.into(), error))
-            })
-        }),
-        slash_action: Some(|ctx| {
-            Box::pin(async move {
-                inner(ctx.into())
-                    .await
-                    .map_err(|error| poise::FrameworkError::new_command(ctx.into(), error))
-            })
-        }),
-        context_menu_action: None,
-        custom_data: Box::new(tag),
-        install_context: Some(vec![InstallationContext::Guild, InstallationContext::User]),
-        interaction_context: Some(vec![
-            InteractionContext::Guild,
-            InteractionContext::BotDm,
-            InteractionContext::PrivateChannel,
-        ]),
-        ..Default::default()
+    let mut removed = 0;
+    if old.keys().any(|name| !new.contains_key(name)) {
+        for command in Command::get_global_commands(ctx).await? {
+            if old.contains_key(&command.name) && !new.contains_key(&command.name) {
+                Command::delete_global_command(ctx, command.id).await?;
+                removed += 1;
+            }
+        }
     }
+
+    Ok((added, removed))
 }
@@ -1,5 +1,4 @@
 use poise::CreateReply;
-use regex::Regex;
 
 use crate::
     commands::{Context, Error}
@@ -8,8 +7,7 @@ use std::fmt::Write;
 
 macro_rules! check {
     ($arg:expr,$regex:expr) => {{
-        let regex = Regex::new($regex).expect("Regex error lmao");
-        regex.is_match($arg)
+        $crate::static_regex!($regex).is_match($arg)
     }};
 }
 
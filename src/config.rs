@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -5,4 +7,23 @@ pub struct Config {
     pub token: String,
     pub quotes_channel: Option<u64>,
     pub log_extensions: Option<Vec<String>>,
+    /// Whether the bot can use the custom emoji in `display.toml`, defaults to true.
+    pub custom_emoji: Option<bool>,
+    /// Toptal API token, hastebin links are only read when one is set.
+    pub hastebin_token: Option<String>,
+    /// Where logs are uploaded to, tried in order until one works. Defaults to mclo.gs.
+    pub upload_backends: Option<Vec<UploadBackendConfig>>,
+    /// Redaction rules to turn on or off by name, such as `ip_addresses = false`. Every rule is
+    /// on by default.
+    pub redaction: Option<HashMap<String, bool>>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum UploadBackendConfig {
+    /// mclo.gs, `api_url` can point to a self-hosted instance.
+    Mclogs { api_url: Option<String> },
+    PasteGg { api_key: Option<String> },
+    /// Stores logs in `directory`, which has to be served at `url` by a web server.
+    Local { directory: String, url: String },
 }
@@ -1,13 +1,67 @@
+use std::time::Duration;
+
 pub(crate) const MCLOGS_API_BASE_URL: &str = "https://api.mclo.gs";
 pub(crate) const FABRIC_META_URL: &str = "https://meta.fabricmc.net/v2";
+pub(crate) const FABRIC_INSTALLER_URL: &str = "https://fabricmc.net/use/installer/";
+pub(crate) const QUILT_META_URL: &str = "https://meta.quiltmc.org/v3";
+pub(crate) const QUILT_INSTALLER_URL: &str = "https://quiltmc.org/en/install/";
+pub(crate) const VERSION_MANIFEST_URL: &str =
+    "https://piston-meta.mojang.com/mc/game/version_manifest_v2.json";
 pub(crate) const MODRINTH_API_URL: &str = "https://api.modrinth.com/v2";
 pub(crate) const MODRINTH_PROJECT_URL: &str = "https://modrinth.com/project";
 pub(crate) const PASTE_GG_API_BASE_URL: &str = "https://api.paste.gg/v1";
+pub(crate) const PASTE_GG_URL: &str = "https://paste.gg";
 pub(crate) const FABRIC_MAVEN_URL: &str = "https://maven.fabricmc.net";
 pub(crate) const PASTEBIN_URL: &str = "https://pastebin.com";
+pub(crate) const GITHUB_API_URL: &str = "https://api.github.com";
+pub(crate) const HASTEBIN_URL: &str = "https://hastebin.com";
+pub(crate) const PASTES_DEV_API_URL: &str = "https://api.pastes.dev";
+pub(crate) const GNOMEBIN_URL: &str = "https://gnomebin.com";
 
 pub(crate) const MAPPINGS_CACHE_LIMIT: usize = 5;
 pub(crate) const MAPPINGS_CACHE_PURGE_SIZE: usize = 3;
 pub(crate) const MAX_LOG_SIZE: u32 = 8_000_000;
+/// How much an uploaded archive can unpack to, in bytes.
+pub(crate) const ARCHIVE_SIZE_LIMIT: u64 = 64_000_000;
+pub(crate) const ARCHIVE_ENTRY_LIMIT: usize = 5_000;
+/// How many logs are taken from a single archive.
+pub(crate) const ARCHIVE_LOG_LIMIT: usize = 4;
+/// How long scanned logs are remembered, so posting them again doesn't upload them again.
+pub(crate) const LOG_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+pub(crate) const LOG_CACHE_LIMIT: usize = 64;
+/// How long to wait for a server to accept a connection before giving up on it.
+pub(crate) const HTTP_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+/// How long an upload can take before the next backend is tried instead.
+pub(crate) const UPLOAD_TIMEOUT: Duration = Duration::from_secs(15);
 
 pub(crate) const MODID_SIZE: usize = 64;
+pub(crate) const REPORT_LIST_LIMIT: usize = 8;
+/// Discord's limits on the parts of a reply, any one of them going over fails the whole reply.
+pub(crate) const EMBED_FIELD_NAME_LIMIT: usize = 256;
+pub(crate) const EMBED_FIELD_VALUE_LIMIT: usize = 1024;
+pub(crate) const BUTTON_LABEL_LIMIT: usize = 80;
+pub(crate) const BUTTON_URL_LIMIT: usize = 512;
+pub(crate) const FIRST_ERROR_LINES: usize = 6;
+
+pub(crate) const MODRINTH_CACHE_TTL: Duration = Duration::from_secs(6 * 60 * 60);
+pub(crate) const MODRINTH_BATCH_SIZE: usize = 100;
+pub(crate) const MODRINTH_LOOKUP_LIMIT: usize = 200;
+/// Version lookups running at once, Modrinth allows around 300 requests a minute.
+pub(crate) const MODRINTH_CONCURRENT_LOOKUPS: usize = 8;
+pub(crate) const LOADER_VERSIONS_TTL: Duration = Duration::from_secs(60 * 60);
+/// How many stable releases a loader can be behind before it's reported as outdated.
+pub(crate) const LOADER_RELEASES_BEHIND: usize = 5;
+pub(crate) const VERSION_MANIFEST_TTL: Duration = Duration::from_secs(6 * 60 * 60);
+/// How many years before the latest release a version of the game is considered end-of-life.
+pub(crate) const END_OF_LIFE_YEARS: u32 = 3;
+
+/// Memory the game needs with no mods installed, in MiB.
+pub(crate) const BASE_MEMORY: u64 = 1024;
+/// Extra memory to allocate for every installed mod, in MiB.
+pub(crate) const MEMORY_PER_MOD: u64 = 16;
+/// Intel drivers with a lower build number than this are reported as outdated.
+pub(crate) const INTEL_MIN_DRIVER: u32 = 27;
+/// AMD drivers with a lower major version than this are reported as outdated.
+pub(crate) const AMD_MIN_DRIVER: u32 = 21;
+/// How many resource packs can be enabled before it's worth pointing out.
+pub(crate) const RESOURCE_PACK_LIMIT: usize = 20;
@@ -0,0 +1,255 @@
+#![feature(async_fn_traits)]
+
+mod archive;
+mod commands;
+mod config;
+mod constants;
+pub mod log_checking;
+mod log_cache;
+mod log_sources;
+pub mod log_upload;
+mod macros;
+pub mod mappings;
+mod redaction;
+mod upload_backends;
+mod util;
+
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::sync::Arc;
+
+use commands::tags::{handle_tag_interaction, handle_tag_message, load_tags, tag_command, Tag};
+use config::Config;
+use log_checking::declarative::{read_declarative_checks, DeclarativeCheck};
+use log_checking::display::{load_display_data, DisplayData};
+use log_checking::incompatibilities::{load_incompatibilities, Incompatibility};
+use log_checking::loader_versions::LoaderVersionCache;
+use log_checking::minecraft::VersionManifestCache;
+use log_checking::updates::UpdateCache;
+use log_cache::LogCache;
+use log_upload::check_for_logs;
+use mappings::cache::MappingsCache;
+use poise::FrameworkOptions;
+use serenity::all::Command;
+use serenity::all::CreateMessage;
+use serenity::all::Interaction;
+use serenity::all::Message;
+use serenity::all::Ready;
+use serenity::async_trait;
+use serenity::prelude::*;
+
+pub struct ConfigData;
+
+impl TypeMapKey for ConfigData {
+    type Value = Config;
+}
+
+pub struct MappingsCacheKey;
+
+impl TypeMapKey for MappingsCacheKey {
+    type Value = MappingsCache;
+}
+
+pub struct LogCacheKey;
+
+impl TypeMapKey for LogCacheKey {
+    type Value = Arc<LogCache>;
+}
+
+pub struct LogChecksKey;
+
+impl TypeMapKey for LogChecksKey {
+    type Value = Vec<DeclarativeCheck>;
+}
+
+pub struct IncompatibilitiesKey;
+
+impl TypeMapKey for IncompatibilitiesKey {
+    type Value = Vec<Incompatibility>;
+}
+
+pub struct TagsKey;
+
+impl TypeMapKey for TagsKey {
+    type Value = HashMap<String, Tag>;
+}
+
+pub struct UpdateCacheKey;
+
+impl TypeMapKey for UpdateCacheKey {
+    type Value = UpdateCache;
+}
+
+pub struct LoaderVersionCacheKey;
+
+impl TypeMapKey for LoaderVersionCacheKey {
+    type Value = LoaderVersionCache;
+}
+
+pub struct VersionManifestKey;
+
+impl TypeMapKey for VersionManifestKey {
+    type Value = VersionManifestCache;
+}
+
+pub struct DisplayDataKey;
+
+impl TypeMapKey for DisplayDataKey {
+    type Value = DisplayData;
+}
+
+struct Handler;
+
+#[async_trait]
+impl EventHandler for Handler {
+    async fn ready(&self, _ctx: Context, event: Ready) {
+        println!("Bot ready! Logged in as {}", event.user.name);
+    }
+
+    async fn message(&self, ctx: Context, message: Message) {
+        match check_for_logs(&ctx, &message, false, false).await {
+            Ok(Some(edit)) => {
+                let reply = CreateMessage::default()
+                    .content(edit.0)
+                    .embeds(edit.1)
+                    .components(edit.2)
+                    .reference_message(&message);
+                if let Err(err) = message.channel_id.send_message(&ctx, reply).await {
+                    println!("Error posting log upload: {err}");
+                }
+            }
+            Ok(None) => {
+                // no-op
+            }
+            Err(err) => {
+                println!("Log uploading threw error: {err}");
+            }
+        };
+    }
+
+    async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
+        if let Interaction::Command(command) = interaction
+            && let Err(err) = handle_tag_interaction(&ctx, &command).await
+        {
+            println!("Error responding to tag: {err}");
+        }
+    }
+}
+
+pub async fn run() {
+    let commands = vec![
+        commands::general::register(),
+        commands::quote::quote(),
+        commands::quote::context_quote(),
+        commands::version::version(),
+        commands::check_logs::check_logs_normal(),
+        commands::check_logs::check_logs_compact(),
+        commands::logdiff::logdiff(),
+        commands::logdiff::logdiff_message(),
+        commands::modversion::modversion(),
+        commands::update_deps::update_deps(),
+        commands::yarn::yarn(),
+        commands::yarn::cache_status(),
+        commands::shortcut::modrinth(),
+        commands::reload::reload(),
+    ];
+
+    let command_names = commands
+        .iter()
+        .map(|command| command.name.as_str())
+        .collect::<Vec<_>>();
+    let (tags, tag_errors) = load_tags(&command_names);
+    let (checks, check_errors) = read_declarative_checks();
+    for (name, err) in &tag_errors {
+        println!("Failed to load tag {name}: {err}");
+    }
+    for (name, err) in &check_errors {
+        println!("Failed to load log check {name}: {err}");
+    }
+    let incompatibilities = load_incompatibilities().unwrap_or_else(|err| {
+        println!("Failed to load incompatibilities: {err}");
+        vec![]
+    });
+
+    let poise_options = FrameworkOptions {
+        commands,
+        on_error: |err| {
+            Box::pin(async move {
+                match err {
+                    // Tags are handled by the event handler instead
+                    poise::FrameworkError::UnknownInteraction { .. } => {}
+                    // Tags used with a mention, like `@bot tag`, end up here
+                    poise::FrameworkError::UnknownCommand {
+                        ctx,
+                        msg,
+                        msg_content,
+                        ..
+                    } => match handle_tag_message(ctx, msg, msg_content).await {
+                        Ok(true) => {}
+                        Ok(false) => println!("Unknown command: {msg_content}"),
+                        Err(err) => println!("Error responding to tag: {err}"),
+                    },
+                    err => println!("{err}"),
+                }
+            })
+        },
+        ..Default::default()
+    };
+
+    let config_file = env::var("CONFIG_FILE").unwrap_or("config.toml".to_string());
+    let config: Config =
+        toml::from_str(&fs::read_to_string(&config_file).expect("reading config"))
+            .expect("parsing config");
+
+    let custom_emoji = config.custom_emoji.unwrap_or(true);
+    let display = load_display_data(custom_emoji).unwrap_or_else(|err| {
+        println!("Failed to load display data: {err}");
+        DisplayData {
+            custom_emoji,
+            ..Default::default()
+        }
+    });
+
+    let framework = poise::Framework::builder()
+        .setup(move |ctx, _ready, framework| {
+            Box::pin(async move {
+                println!("Registering commands");
+                let mut commands =
+                    poise::builtins::create_application_commands(&framework.options().commands);
+                if let Some(tags) = ctx.data.read().await.get::<TagsKey>() {
+                    commands.extend(tags.keys().map(|name| tag_command(name)));
+                }
+                Command::set_global_commands(ctx, commands).await?;
+                Ok(ConfigData {})
+            })
+        })
+        .options(poise_options)
+        .build();
+
+    // Login with a bot token from the environment
+    let intents = GatewayIntents::non_privileged() | GatewayIntents::MESSAGE_CONTENT;
+    let mut client = Client::builder(&config.token, intents)
+        .event_handler(Handler)
+        .framework(framework)
+        .await
+        .expect("Error creating client");
+    {
+        let mut data_lock = client.data.write().await;
+        data_lock.insert::<ConfigData>(config);
+        data_lock.insert::<MappingsCacheKey>(MappingsCache::create());
+        data_lock.insert::<LogCacheKey>(Arc::new(LogCache::create()));
+        data_lock.insert::<UpdateCacheKey>(UpdateCache::create());
+        data_lock.insert::<LoaderVersionCacheKey>(LoaderVersionCache::create());
+        data_lock.insert::<VersionManifestKey>(VersionManifestCache::create());
+        data_lock.insert::<LogChecksKey>(checks);
+        data_lock.insert::<IncompatibilitiesKey>(incompatibilities);
+        data_lock.insert::<TagsKey>(tags);
+        data_lock.insert::<DisplayDataKey>(display);
+    }
+
+    // start listening for events by starting a single shard
+    if let Err(why) = client.start().await {
+        println!("An error occurred while running the client: {why:?}");
+    }
+}
@@ -0,0 +1,238 @@
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use serenity::builder::CreateEmbed;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    constants::{LOG_CACHE_LIMIT, LOG_CACHE_TTL},
+    log_checking::{checks::CheckAction, environment::read_mc_version},
+    log_upload::{LogType, MapStatus},
+    mappings::cache::MappingsCache,
+};
+
+/// The sha256 of a log as it was posted.
+pub(crate) type LogHash = [u8; 32];
+
+/// (hash of the log as it was posted, yarn build it was remapped with)
+pub(crate) type LogKey = (LogHash, Option<String>);
+
+/// The embed shown for a log, and the buttons under it.
+pub(crate) type Report = (CreateEmbed, Vec<CheckAction>);
+
+/// A log that's already been remapped, redacted and uploaded.
+#[derive(Clone)]
+pub(crate) struct ProcessedLog {
+    pub t: LogType,
+    pub map_status: MapStatus,
+    pub url: String,
+    pub log: String,
+    pub redacted: usize,
+}
+
+struct CacheEntry<T> {
+    stored: Instant,
+    value: T,
+}
+
+/// Remembers logs that were recently scanned, so posting the same log again, or checking the
+/// same message twice, doesn't download, remap and upload it again. Entries expire after
+/// [`LOG_CACHE_TTL`].
+pub struct LogCache {
+    /// How long entries are kept, [`LOG_CACHE_TTL`] outside of tests.
+    ttl: Duration,
+    logs: Mutex<HashMap<LogKey, CacheEntry<ProcessedLog>>>,
+    /// Links that have been read, pointing to the log they had.
+    links: Mutex<HashMap<String, CacheEntry<LogKey>>>,
+    /// Reports by the hash of the log they were made for, retitled when they're reused.
+    reports: Mutex<HashMap<LogHash, CacheEntry<Report>>>,
+}
+
+fn hash_log(log: &str) -> LogHash {
+    Sha256::digest(log).into()
+}
+
+fn get<K: Eq + Hash, V: Clone>(
+    cache: &Mutex<HashMap<K, CacheEntry<V>>>,
+    key: &K,
+    ttl: Duration,
+) -> Option<V> {
+    let cache = cache.lock().expect("Log cache poisoned");
+    cache
+        .get(key)
+        .filter(|entry| entry.stored.elapsed() < ttl)
+        .map(|entry| entry.value.clone())
+}
+
+/// Inserts a value, making room by dropping expired entries and then the oldest ones.
+fn insert<K: Eq + Hash + Clone, V>(
+    cache: &Mutex<HashMap<K, CacheEntry<V>>>,
+    key: K,
+    value: V,
+    ttl: Duration,
+) {
+    let mut cache = cache.lock().expect("Log cache poisoned");
+    if cache.len() >= LOG_CACHE_LIMIT {
+        cache.retain(|_, entry| entry.stored.elapsed() < ttl);
+    }
+    while cache.len() >= LOG_CACHE_LIMIT {
+        let Some(oldest) = cache
+            .iter()
+            .min_by_key(|(_, entry)| entry.stored)
+            .map(|(key, _)| key.clone())
+        else {
+            break;
+        };
+        cache.remove(&oldest);
+    }
+    cache.insert(
+        key,
+        CacheEntry {
+            stored: Instant::now(),
+            value,
+        },
+    );
+}
+
+impl LogCache {
+    pub fn create() -> LogCache {
+        LogCache::with_ttl(LOG_CACHE_TTL)
+    }
+
+    fn with_ttl(ttl: Duration) -> LogCache {
+        LogCache {
+            ttl,
+            logs: Mutex::new(HashMap::new()),
+            links: Mutex::new(HashMap::new()),
+            reports: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The key for a log as it was posted. Changes once mappings for it are downloaded, or when a
+    /// newer yarn build replaces them, so the log is remapped again.
+    pub(crate) fn key(log: &str, mappings_cache: &MappingsCache) -> LogKey {
+        let yarn_version = read_mc_version(log)
+            .and_then(|mc_version| mappings_cache.yarn_version(&mc_version).map(str::to_string));
+        (hash_log(log), yarn_version)
+    }
+
+    pub(crate) fn log(&self, key: &LogKey) -> Option<ProcessedLog> {
+        get(&self.logs, key, self.ttl)
+    }
+
+    pub(crate) fn insert_log(&self, key: LogKey, log: ProcessedLog) {
+        insert(&self.logs, key, log, self.ttl);
+    }
+
+    pub(crate) fn linked_log(&self, url: &str) -> Option<ProcessedLog> {
+        let key = get(&self.links, &url.to_string(), self.ttl)?;
+        self.log(&key)
+    }
+
+    pub(crate) fn insert_link(&self, url: &str, key: LogKey) {
+        insert(&self.links, url.to_string(), key, self.ttl);
+    }
+
+    pub(crate) fn report(&self, log: &str) -> Option<Report> {
+        get(&self.reports, &hash_log(log), self.ttl)
+    }
+
+    pub(crate) fn insert_report(&self, log: &str, report: Report) {
+        insert(&self.reports, hash_log(log), report, self.ttl);
+    }
+
+    /// Forgets every report, for when the checks change.
+    pub(crate) fn clear_reports(&self) {
+        self.reports.lock().expect("Log cache poisoned").clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mappings::Mappings;
+
+    const LOG: &str = "[12:00:00] [main/INFO]: Loading Minecraft 1.20.1 with Fabric Loader 0.15.11";
+
+    fn url(i: usize) -> String {
+        format!("https://mclo.gs/{i}")
+    }
+
+    fn key(log: &str) -> LogKey {
+        (hash_log(log), None)
+    }
+
+    /// Makes an entry look like it was stored a while ago.
+    fn age<K: Eq + Hash, V>(cache: &Mutex<HashMap<K, CacheEntry<V>>>, key: &K, age: Duration) {
+        let mut cache = cache.lock().unwrap();
+        let entry = cache.get_mut(key).unwrap();
+        entry.stored = entry.stored.checked_sub(age).unwrap();
+    }
+
+    #[test]
+    fn expiry() {
+        let ttl = Duration::from_secs(10);
+        let cache = LogCache::with_ttl(ttl);
+        cache.insert_link(&url(0), key("fresh"));
+        cache.insert_link(&url(1), key("expired"));
+        age(&cache.links, &url(1), ttl);
+
+        assert_eq!(get(&cache.links, &url(0), ttl), Some(key("fresh")));
+        assert_eq!(get(&cache.links, &url(1), ttl), None);
+    }
+
+    #[test]
+    fn eviction() {
+        let ttl = Duration::from_secs(10);
+        let cache = LogCache::with_ttl(ttl);
+        for i in 0..LOG_CACHE_LIMIT {
+            cache.insert_link(&url(i), key(&i.to_string()));
+            age(
+                &cache.links,
+                &url(i),
+                Duration::from_millis((LOG_CACHE_LIMIT - i) as u64),
+            );
+        }
+
+        // The oldest entry makes room when it's full
+        cache.insert_link(&url(LOG_CACHE_LIMIT), key("new"));
+        assert_eq!(cache.links.lock().unwrap().len(), LOG_CACHE_LIMIT);
+        assert_eq!(get(&cache.links, &url(0), ttl), None);
+        assert!(get(&cache.links, &url(1), ttl).is_some());
+
+        // Unless there are expired ones
+        age(&cache.links, &url(5), ttl);
+        cache.insert_link(&url(LOG_CACHE_LIMIT + 1), key("newer"));
+        assert!(!cache.links.lock().unwrap().contains_key(&url(5)));
+        assert!(get(&cache.links, &url(1), ttl).is_some());
+        assert!(get(&cache.links, &url(LOG_CACHE_LIMIT + 1), ttl).is_some());
+    }
+
+    #[test]
+    fn key_follows_yarn_build() {
+        let mappings = || Mappings {
+            full_classes: HashMap::new(),
+            partial_classes: HashMap::new(),
+            methods: HashMap::new(),
+            fields: HashMap::new(),
+        };
+        let mut mappings_cache = MappingsCache::create();
+        let unmapped = LogCache::key(LOG, &mappings_cache);
+        assert_eq!(unmapped, key(LOG));
+        assert_ne!(
+            unmapped,
+            LogCache::key(&format!("{LOG}\n"), &mappings_cache)
+        );
+
+        mappings_cache.insert("1.20.1", "1.20.1+build.9".to_string(), mappings());
+        let mapped = LogCache::key(LOG, &mappings_cache);
+        assert_eq!(mapped, (hash_log(LOG), Some("1.20.1+build.9".to_string())));
+
+        mappings_cache.insert("1.20.1", "1.20.1+build.10".to_string(), mappings());
+        assert_ne!(LogCache::key(LOG, &mappings_cache), mapped);
+    }
+}
@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+
+use crate::static_regex;
+
+use super::environment::EnvironmentContext;
+
+/// Frames closer to where the exception was thrown count for more, down to a weight of 1.
+const TOP_FRAME_WEIGHT: usize = 10;
+/// Mixins applied to a class in the stack are a weaker hint than the mod's own code being there.
+const APPLIED_MIXIN_WEIGHT: usize = 1;
+/// Weight of a mod listed in a crash report's "Mixins in Stacktrace" section.
+const MIXINS_IN_STACKTRACE_WEIGHT: usize = 3;
+
+/// Jars belonging to the loader, the game or the launcher, which show up in nearly every trace.
+const IGNORED_JARS: &[&str] = &[
+    "fabric-loader",
+    "quilt-loader",
+    "sponge-mixin",
+    "client-intermediary",
+    "intermediary",
+    "minecraft",
+    "client",
+    "server",
+    "forge",
+    "neoforge",
+    "fmlloader",
+    "fmlcore",
+    "javafmllanguage",
+    "loader",
+    "modlauncher",
+    "bootstraplauncher",
+    "securejarhandler",
+    "eventbus",
+    "NewLaunch",
+];
+
+const IGNORED_MODS: &[&str] = &[
+    "minecraft",
+    "java",
+    "fabricloader",
+    "quilt_loader",
+    "forge",
+    "neoforge",
+    "mixinextras",
+];
+
+pub struct Suspect {
+    pub mod_id: String,
+    pub score: usize,
+    /// How many times the mod was seen, across frames and mixin notes.
+    pub hits: usize,
+    /// 1-based log line where the mod was first seen.
+    pub line: usize,
+}
+
+fn normalize(name: &str) -> String {
+    name.to_lowercase().replace(['-', '.', '+'], "_")
+}
+
+/// Finds the discovered mod a jar file belongs to, by matching the longest run of name segments
+/// against the mod ids, so `sodium-fabric-0.5.8` resolves to `sodium`.
+fn resolve_jar(jar: &str, ctx: &EnvironmentContext) -> Option<String> {
+    if jar.starts_with(|c: char| c.is_ascii_digit())
+        || IGNORED_JARS
+            .iter()
+            .any(|ignored| jar == *ignored || jar.starts_with(&format!("{ignored}-")))
+    {
+        return None;
+    }
+
+    let normalized = normalize(jar);
+    let segments = normalized.split('_').collect::<Vec<_>>();
+    (1..=segments.len())
+        .rev()
+        .map(|n| segments[..n].join("_"))
+        .find_map(|prefix| {
+            ctx.discovered_mods
+                .iter()
+                .find(|m| normalize(m.id) == prefix)
+                .map(|m| m.id.to_string())
+        })
+}
+
+/// Mod ids from mixin metadata are usually exact, but are matched against the mod list anyway
+/// to line up their spelling with the other sources.
+fn resolve_id(id: &str, ctx: &EnvironmentContext) -> String {
+    let normalized = normalize(id);
+    ctx.discovered_mods
+        .iter()
+        .find(|m| normalize(m.id) == normalized)
+        .map_or_else(|| id.to_string(), |m| m.id.to_string())
+}
+
+/// Names of the mods a single frame points at, along with whether it's the mod's own code.
+fn frame_mods(method: &str, extra: Option<&str>, ctx: &EnvironmentContext) -> Vec<(String, bool)> {
+    let mut mods = vec![];
+
+    // Mixin handlers are named like `handler$zza000$modid$method`
+    let name = method.rsplit('.').next().unwrap_or(method);
+    if let Some(captures) = static_regex!(r"^[a-zA-Z]+\$[a-z0-9]+\$([a-z0-9_]+)\$").captures(name)
+    {
+        mods.push((resolve_id(&captures[1], ctx), true));
+    }
+
+    if let Some(extra) = extra {
+        if let Some(captures) = static_regex!(r"^~?\[([^\[\]:/%]+?)\.jar").captures(extra)
+            && let Some(mod_id) = resolve_jar(&captures[1], ctx)
+        {
+            mods.push((mod_id, true));
+        }
+        for captures in static_regex!(r"from mod ([\w-]+)").captures_iter(extra) {
+            mods.push((resolve_id(&captures[1], ctx), false));
+        }
+        // Forge lists every mixin config applied to the class, like `pl:mixin:APP:sodium.mixins.json:...`
+        for captures in static_regex!(r"pl:mixin:APP:([\w.-]+?)\.json").captures_iter(extra)
+        {
+            if let Some(mod_id) = resolve_jar(&captures[1], ctx) {
+                mods.push((mod_id, false));
+            }
+        }
+    }
+
+    mods
+}
+
+/// Ranks mods by how often and how close to the top they appear in the root cause of every
+/// exception from `from_line` onwards, and in the crash report's "Mixins in Stacktrace" section.
+pub fn suspect_mods(log: &str, ctx: &EnvironmentContext, from_line: usize) -> Vec<Suspect> {
+    let mut suspects: HashMap<String, Suspect> = HashMap::new();
+    let mut add = |mod_id: String, score: usize, line: usize| {
+        if IGNORED_MODS.contains(&mod_id.as_str()) {
+            return;
+        }
+        let suspect = suspects.entry(mod_id.clone()).or_insert(Suspect {
+            mod_id,
+            score: 0,
+            hits: 0,
+            line,
+        });
+        suspect.score += score;
+        suspect.hits += 1;
+        suspect.line = suspect.line.min(line);
+    };
+
+    for exception in ctx.exceptions.iter().filter(|e| e.line >= from_line) {
+        let root = exception.root_cause();
+        for (depth, frame) in root.frames.iter().enumerate() {
+            for (mod_id, own_code) in frame_mods(frame.method, frame.extra, ctx) {
+                let score = if own_code {
+                    TOP_FRAME_WEIGHT.saturating_sub(depth).max(1)
+                } else {
+                    APPLIED_MIXIN_WEIGHT
+                };
+                add(mod_id, score, frame.line);
+            }
+        }
+    }
+
+    if let Some(section) = log.find("Mixins in Stacktrace:") {
+        let start_line = log[..section].matches('\n').count() + 1;
+        for (i, line) in log[section..]
+            .lines()
+            .skip(1)
+            .take_while(|l| !l.trim().is_empty())
+            .enumerate()
+        {
+            if let Some(captures) = static_regex!(r"\(from mod ([\w-]+)\)").captures(line) {
+                add(
+                    resolve_id(&captures[1], ctx),
+                    MIXINS_IN_STACKTRACE_WEIGHT,
+                    start_line + i + 1,
+                );
+            }
+        }
+    }
+
+    let mut suspects = suspects.into_values().collect::<Vec<_>>();
+    suspects.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.mod_id.cmp(&b.mod_id)));
+    suspects
+}
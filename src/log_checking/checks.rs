@@ -1,11 +1,26 @@
-use crate::{constants::MODID_SIZE, expect, grab, grab_all, peek, truncate};
+use crate::{
+    constants::{
+        BASE_MEMORY, BUTTON_URL_LIMIT, FABRIC_INSTALLER_URL, FIRST_ERROR_LINES, LOADER_RELEASES_BEHIND,
+        MEMORY_PER_MOD, MODID_SIZE, QUILT_INSTALLER_URL, REPORT_LIST_LIMIT, RESOURCE_PACK_LIMIT,
+    },
+    expect, grab_all, grab_every, peek, static_regex, truncate,
+};
 
-use super::environment::{EnvironmentContext, Launcher, ModLoader};
-use regex::Regex;
-use std::{collections::HashSet, fmt::Write};
+use super::{
+    blame::suspect_mods,
+    declarative::DeclarativeCheck,
+    environment::{java_major_version, EnvironmentContext, Launcher, ModLoader},
+    incompatibilities::Incompatibility,
+    minecraft::VersionType,
+    records::Level,
+    versions::version_numbers,
+};
+use regex::{Captures, RegexSet};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, fmt::Write, sync::LazyLock};
 
 #[allow(dead_code)]
-#[derive(PartialEq, PartialOrd, Eq, Ord, Clone, Copy)]
+#[derive(PartialEq, PartialOrd, Eq, Ord, Hash, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum Severity {
     None,
     Medium,
@@ -20,105 +35,595 @@ impl Severity {
             Severity::High => 0x00d6_2828,
         }
     }
-
-    pub fn get_emoji(&self) -> &'static str {
-        match self {
-            Severity::None => "<:severity_none:1246879605399228449>",
-            Severity::Medium => "<:severity_medium:1246879606993190972>",
-            Severity::High => "<:severity_high:1246879607869935678>",
-        }
-    }
 }
 
+#[derive(Serialize)]
 pub struct CheckReport {
+    /// Stable identifier of the finding, unlike the title this never changes.
+    pub id: String,
     pub title: String,
     pub description: String,
     pub severity: Severity,
+    pub evidence: Option<Evidence>,
+    pub actions: Vec<CheckAction>,
+}
+
+/// The log line a report was triggered by.
+#[derive(Serialize, Clone)]
+pub struct Evidence {
+    /// 1-based line number in the (remapped) log.
+    pub line: usize,
+    pub excerpt: String,
+}
+
+impl Evidence {
+    pub fn from_captures(log: &str, captures: &Captures) -> Evidence {
+        let start = captures.get(0).expect("Regex err").start();
+        let line_start = log[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = log[start..].find('\n').map_or(log.len(), |i| start + i);
+
+        Evidence {
+            line: log[..start].matches('\n').count() + 1,
+            excerpt: truncate!(log[line_start..line_end].trim_end().to_string(), 256),
+        }
+    }
+
+    /// Evidence for a 1-based line number, like the ones the stack trace parser records.
+    pub fn from_line(log: &str, line: usize) -> Evidence {
+        Evidence {
+            line,
+            excerpt: truncate!(
+                log.lines().nth(line - 1).unwrap_or_default().trim_end().to_string(),
+                256
+            ),
+        }
+    }
+}
+
+/// Something the user can do to resolve a report, rendered as a link button.
+#[derive(Serialize, Clone, PartialEq, Deserialize)]
+pub struct CheckAction {
+    pub label: String,
+    pub url: String,
+}
+
+impl CheckAction {
+    pub fn new(label: impl Into<String>, url: impl Into<String>) -> CheckAction {
+        CheckAction {
+            label: label.into(),
+            url: url.into(),
+        }
+    }
+
+    /// Whether Discord accepts the url for a link button, urls filled in by checks from the log
+    /// can be anything.
+    pub fn has_valid_url(&self) -> bool {
+        self.url.len() <= BUTTON_URL_LIMIT
+            && reqwest::Url::parse(&self.url)
+                .is_ok_and(|url| matches!(url.scheme(), "http" | "https"))
+    }
 }
 
-pub fn check_checks(log: &str, ctx: &EnvironmentContext) -> Vec<CheckReport> {
-    [
-        crash_report_analysis,
-        dependency_generic,
-        crash_generic,
-        mixin_conflicts,
-        duck_fail,
-        class_missing_generic,
-        broken_modmenu,
-        frozen_registry,
-        failed_registry,
-        java,
-        jdk,
-        broken_cicada_config,
-        missing_field,
-        datapacks_failed,
-        resource_files,
-        disk_full,
-        quilt,
-        polymc,
-        optifabric,
-        bclib,
-        feather,
-        mcreator,
-        indium,
-    ]
-    .iter()
-    .filter_map(|check| check(log, ctx))
-    .collect()
-}
-
-pub fn crash_report_analysis(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
+fn download_java(version: Option<&str>) -> CheckAction {
+    match version {
+        Some(version) => CheckAction::new(
+            format!("Download Java {version}"),
+            format!("https://adoptium.net/temurin/releases/?version={version}"),
+        ),
+        None => CheckAction::new("Download Java", "https://adoptium.net/temurin/releases/"),
+    }
+}
+
+/// Groups values by key, keeping the order in which keys and values first occurred and
+/// dropping duplicate values.
+fn group_by_key(pairs: impl IntoIterator<Item = (String, String)>) -> Vec<(String, Vec<String>)> {
+    let mut groups: Vec<(String, Vec<String>)> = vec![];
+    for (key, value) in pairs {
+        match groups.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, values)) => {
+                if !values.contains(&value) {
+                    values.push(value);
+                }
+            }
+            None => groups.push((key, vec![value])),
+        }
+    }
+    groups
+}
+
+/// Writes a markdown list of at most [`REPORT_LIST_LIMIT`] items, summarizing the rest.
+pub(super) fn write_list(description: &mut String, items: impl ExactSizeIterator<Item = String>) {
+    let total = items.len();
+    for ele in items.take(REPORT_LIST_LIMIT) {
+        let _ = write!(description, "- {ele}\n");
+    }
+    if total > REPORT_LIST_LIMIT {
+        let _ = write!(description, "- and {} more...\n", total - REPORT_LIST_LIMIT);
+    }
+}
+
+fn code_list(values: &[String]) -> String {
+    values
+        .iter()
+        .map(|v| format!("`{v}`"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn search_modrinth(query: &str) -> CheckAction {
+    CheckAction::new(
+        truncate!(format!("Search {query}"), 80),
+        format!(
+            "https://modrinth.com/mods?{}",
+            serde_urlencoded::to_string([("q", query)]).unwrap_or_default()
+        ),
+    )
+}
+
+/// A hardcoded check, along with the patterns of which at least one has to occur in a log for it
+/// to possibly report anything. Checks that only look at the environment have no triggers and
+/// always run.
+struct Check {
+    run: fn(&str, &EnvironmentContext) -> Option<CheckReport>,
+    triggers: &'static [&'static str],
+}
+
+static CHECKS: &[Check] = &[
+    Check {
+        run: crash_report_analysis,
+        triggers: &[
+            r"---- Minecraft Crash Report ----",
+            r"Minecraft has crashed!",
+            r"Unreported exception thrown!",
+            r"This crash report has been saved to:",
+        ],
+    },
+    Check {
+        run: suspected_mods,
+        triggers: &[
+            r"---- Minecraft Crash Report ----",
+            r"Minecraft has crashed!",
+            r"Unreported exception thrown!",
+            r"Could not execute entrypoint stage",
+        ],
+    },
+    Check {
+        run: dependency_generic,
+        triggers: &[r"which is missing!", r"but a matching version is present"],
+    },
+    Check {
+        run: crash_generic,
+        triggers: &[
+            r"java\.lang\.Error: Watchdog",
+            r"java\.lang\.OutOfMemoryError:",
+            r"Error creating Mixin config",
+            r"InvalidInjectionException",
+            r"InvalidAccessorException",
+            r"MixinApplyError",
+            r"Mixin apply for mod",
+            r"Could not execute entrypoint stage",
+        ],
+    },
+    Check {
+        run: mixin_conflicts,
+        triggers: &[r"@Redirect conflict"],
+    },
+    Check {
+        run: duck_fail,
+        triggers: &[r"AbstractMethodError"],
+    },
+    Check {
+        run: class_missing_generic,
+        triggers: &[r"java\.lang\.ClassNotFoundException"],
+    },
+    Check {
+        run: broken_modmenu,
+        triggers: &[r"provides a broken implementation of ModMenuApi"],
+    },
+    Check {
+        run: frozen_registry,
+        triggers: &[r"Registry is already frozen"],
+    },
+    Check {
+        run: failed_registry,
+        triggers: &[r"Failed to load registries due to above errors"],
+    },
+    Check {
+        run: java,
+        triggers: &[r"\(java\) [0-9]+ with version", r"UnsupportedClassVersionError"],
+    },
+    Check {
+        run: game_java,
+        triggers: &[],
+    },
+    Check {
+        run: snapshot,
+        triggers: &[],
+    },
+    Check {
+        run: end_of_life,
+        triggers: &[],
+    },
+    Check {
+        run: jdk,
+        triggers: &[r"No compatible attachment provider is available"],
+    },
+    Check {
+        run: broken_cicada_config,
+        triggers: &[r"\[cicada\] Failed to parse config file"],
+    },
+    Check {
+        run: missing_field,
+        triggers: &[r"java\.lang\.NoSuchFieldError"],
+    },
+    Check {
+        run: datapacks_failed,
+        triggers: &[r"Failed to load datapacks"],
+    },
+    Check {
+        run: resource_files,
+        triggers: &[r"Failed to parse \S+ from pack"],
+    },
+    Check {
+        run: disk_full,
+        triggers: &[r"No space left on device"],
+    },
+    Check {
+        run: low_memory,
+        triggers: &[r"-- System Details --"],
+    },
+    Check {
+        run: software_renderer,
+        triggers: &[r"-- System Details --"],
+    },
+    Check {
+        run: outdated_gpu_driver,
+        triggers: &[r"-- System Details --"],
+    },
+    Check {
+        run: resource_packs,
+        triggers: &[r"-- System Details --"],
+    },
+    Check {
+        run: first_error,
+        triggers: &[r"/(?:ERROR|SEVERE|FATAL)\]"],
+    },
+    Check {
+        run: outdated_mods,
+        triggers: &[],
+    },
+    Check {
+        run: outdated_loader,
+        triggers: &[],
+    },
+    Check {
+        run: quilt,
+        triggers: &[],
+    },
+    Check {
+        run: polymc,
+        triggers: &[],
+    },
+    Check {
+        run: tlauncher,
+        triggers: &[],
+    },
+    Check {
+        run: mcreator,
+        triggers: &[r"at net\.mcreator\."],
+    },
+    Check {
+        run: indium,
+        triggers: &[r"RendererAccess\.getRenderer"],
+    },
+];
+
+/// Every check trigger in a single set, along with the index of the check each pattern belongs to.
+static TRIGGERS: LazyLock<(RegexSet, Vec<usize>)> = LazyLock::new(|| {
+    let (owners, patterns): (Vec<_>, Vec<_>) = CHECKS
+        .iter()
+        .enumerate()
+        .flat_map(|(i, check)| check.triggers.iter().map(move |t| (i, *t)))
+        .unzip();
+    (RegexSet::new(patterns).expect("Incorrect regex"), owners)
+});
+
+pub fn check_checks(
+    log: &str,
+    ctx: &EnvironmentContext,
+    declarative_checks: &[DeclarativeCheck],
+    incompatibilities: &[Incompatibility],
+) -> Vec<CheckReport> {
+    // One pass over the log rules out every check that can't possibly match
+    let (set, owners) = &*TRIGGERS;
+    let mut triggered = vec![false; CHECKS.len()];
+    for i in set.matches(log).iter() {
+        triggered[owners[i]] = true;
+    }
+
+    let mut reports: Vec<CheckReport> = CHECKS
+        .iter()
+        .zip(triggered)
+        .filter(|(check, triggered)| *triggered || check.triggers.is_empty())
+        .filter_map(|(check, _)| (check.run)(log, ctx))
+        .chain(incompatibilities.iter().filter_map(|i| i.check(log, ctx)))
+        .collect();
+    let declarative = declarative_checks
+        .iter()
+        .filter_map(|check| check.check(log, ctx, &reports))
+        .collect::<Vec<_>>();
+    reports.extend(declarative);
+    reports
+}
+
+/// Points out the root cause of the first exception after the given line, unless that exception
+/// has no cause of its own.
+fn describe_root_cause(ctx: &EnvironmentContext, line: usize) -> String {
+    let Some(exception) = ctx.exceptions.iter().find(|e| e.line > line) else {
+        return String::new();
+    };
+    let root = exception.root_cause();
+    if std::ptr::eq(root, exception) {
+        return String::new();
+    }
+
+    let mut description = format!(
+        "\nRoot cause:\n```\n{}",
+        truncate!(root.to_string(), 512)
+    );
+    if let Some(frame) = root.frames.first() {
+        let _ = write!(description, "\n\tat {}", truncate!(frame.method.to_string(), 256));
+    }
+    description.push_str("\n```");
+    description
+}
+
+pub fn crash_report_analysis(log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
     if let Some(captures) = grab_all!(
         log,
         r"---- Minecraft Crash Report ----(?:\r\n|\r|\n)// .+(?:\r\n|\r|\n)(?:\r\n|\r|\n)Time: .+(?:\r\n|\r|\n)Description: (.+)(?:\r\n|\r|\n)(?:\r\n|\r|\n)(.+)(?:\r\n|\r|\n)"
     ) {
         let description = expect!(captures, 1, 256);
         let error = expect!(captures, 2, 512);
+        let evidence = Evidence::from_captures(log, &captures);
+        let root_cause = describe_root_cause(ctx, evidence.line);
         return Some(CheckReport {
+            id: "crash_report".to_string(),
             title: "Crash report analysis".to_string(),
-            description: format!("Context: `{description}`\n```\n{error}\n```"),
+            description: format!("Context: `{description}`\n```\n{error}\n```{root_cause}"),
             severity: Severity::High,
+            evidence: Some(evidence),
+            actions: vec![],
         });
     }
 
-    if let Some(Some(error)) = grab!(
+    if let Some(captures) = grab_all!(
         log,
-        512,
         r"Minecraft has crashed!(?:\r\n|\r|\n)(.+)(?:\r\n|\r|\n)",
         r"Unreported exception thrown!(?:\r\n|\r|\n)(.+)(?:\r\n|\r|\n)"
     ) {
+        let error = expect!(captures, 1, 512);
+        let evidence = Evidence::from_captures(log, &captures);
+        let root_cause = describe_root_cause(ctx, evidence.line);
         return Some(CheckReport {
+            id: "crash_detected".to_string(),
             title: "Crash detected".to_string(),
-            description: format!("```{error}```"),
+            description: format!("```{error}```{root_cause}"),
             severity: Severity::High,
+            evidence: Some(evidence),
+            actions: vec![],
         });
     }
 
-    if grab!(log, 512, r"This crash report has been saved to:").is_some() {
+    if let Some(captures) = grab_all!(log, r"This crash report has been saved to:") {
         return Some(CheckReport {
+            id: "crash_unknown".to_string(),
             title: "Crash detected".to_string(),
             description: "No details could be determined automatically.".to_string(),
             severity: Severity::High,
+            evidence: Some(Evidence::from_captures(log, &captures)),
+            actions: vec![],
         });
     }
     None
 }
 
-pub fn dependency_generic(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
-    if let Some(captures) = grab_all!(
+pub fn suspected_mods(log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    let crash = grab_all!(
         log,
-        r"Mod '(.+)' \(\S+\) \S+ requires any version between \S+ and \S+ of (.+), which is missing!",
-        r"Mod '(.+)' \(\S+\) \S+ requires version \S+ or later of (.+), which is missing!",
-        r"Mod '(.+)' \(\S+\) \S+ requires any version of (.+), which is missing!"
-    ) {
-        let dependent = expect!(captures, 1, MODID_SIZE);
-        let dependency = expect!(captures, 2, MODID_SIZE);
+        r"---- Minecraft Crash Report ----",
+        r"Minecraft has crashed!",
+        r"Unreported exception thrown!",
+        r"Could not execute entrypoint stage"
+    )?;
+    let crash_line = Evidence::from_captures(log, &crash).line;
+    let suspects = suspect_mods(log, ctx, crash_line);
+
+    let description = match suspects.as_slice() {
+        [] => return None,
+        [suspect] => format!(
+            "The mod `{}` appears in the stack trace of the crash, and is the most likely cause.",
+            suspect.mod_id
+        ),
+        _ => {
+            let mut description = "The mods below appear in the stack trace of the crash, the ones at the top are the most likely cause.\n".to_string();
+            write_list(
+                &mut description,
+                suspects.iter().map(|s| match s.hits {
+                    1 => format!("`{}`", s.mod_id),
+                    hits => format!("`{}` (seen {hits} times)", s.mod_id),
+                }),
+            );
+            description
+        }
+    };
+
+    Some(CheckReport {
+        id: "suspected_mods".to_string(),
+        title: "Suspected mods".to_string(),
+        description,
+        severity: Severity::Medium,
+        evidence: Some(Evidence::from_line(log, suspects[0].line)),
+        actions: vec![],
+    })
+}
+
+pub fn outdated_mods(_log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    if ctx.updates.is_empty() {
+        return None;
+    }
+
+    let mut description = format!(
+        "{} installed mods have a newer release on Modrinth for this version of the game, updating them may fix issues.\n",
+        ctx.updates.len()
+    );
+    write_list(
+        &mut description,
+        ctx.updates.iter().map(|update| {
+            format!(
+                "`{}` `{}` → [`{}`]({})",
+                update.mod_id, update.installed, update.latest, update.url
+            )
+        }),
+    );
+
+    Some(CheckReport {
+        id: "outdated_mods".to_string(),
+        title: "Outdated mods".to_string(),
+        description,
+        severity: Severity::None,
+        evidence: None,
+        actions: vec![],
+    })
+}
+
+pub fn outdated_loader(log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    let (name, installed, installer) = match &ctx.loader {
+        Some(ModLoader::Fabric(Some(version))) => ("Fabric Loader", version, FABRIC_INSTALLER_URL),
+        Some(ModLoader::Quilt(Some(version))) => ("Quilt Loader", version, QUILT_INSTALLER_URL),
+        _ => return None,
+    };
+    let installed_numbers = version_numbers(installed, None)?;
+    let actions = vec![CheckAction::new(format!("{name} installer"), installer)];
+
+    // A mod needing a newer loader is reported as a mismatched dependency, rather than a missing one
+    let required = grab_every!(
+        log,
+        r"Mod '(.+?)' \(\S+\) \S+ requires version (\S+) or later of (?:mod '[^']+' \()?(?:fabricloader|quilt_loader)\b",
+        r"Mod '(.+?)' \(\S+\) \S+ requires any version between (\S+) .*?of (?:mod '[^']+' \()?(?:fabricloader|quilt_loader)\b"
+    )
+    .into_iter()
+    .filter_map(|captures| {
+        let numbers = version_numbers(captures.get(2)?.as_str(), None)?;
+        (numbers > installed_numbers).then_some((numbers, captures))
+    })
+    .max_by(|(a, _), (b, _)| a.cmp(b));
+    if let Some((_, captures)) = required {
+        let mod_name = expect!(captures, 1, MODID_SIZE);
+        let version = expect!(captures, 2, MODID_SIZE);
         return Some(CheckReport {
-            title: "Missing dependency".to_string(),
+            id: "outdated_loader".to_string(),
+            title: format!("Outdated {name}"),
             description: format!(
-                "The `{dependent}` mod needs `{dependency}` to be installed, but it is missing."
+                "The `{mod_name}` mod requires {name} `{version}` or newer, but `{installed}` is installed. Update the loader with the installer, or in your launcher's instance settings."
             ),
             severity: Severity::High,
+            evidence: Some(Evidence::from_captures(log, &captures)),
+            actions,
+        });
+    }
+
+    let behind = ctx
+        .loader_versions
+        .iter()
+        .filter(|v| version_numbers(v, None).is_some_and(|n| n > installed_numbers))
+        .count();
+    if behind < LOADER_RELEASES_BEHIND {
+        return None;
+    }
+    Some(CheckReport {
+        id: "outdated_loader".to_string(),
+        title: format!("Outdated {name}"),
+        description: format!(
+            "{name} `{installed}` is {behind} releases behind the latest version, `{}`. Consider updating it, as many newer mods require a recent version of the loader.",
+            ctx.loader_versions.first()?
+        ),
+        severity: Severity::Medium,
+        evidence: None,
+        actions,
+    })
+}
+
+/// Follows up on an error attributed to `mod_id` with what the stack trace says about it.
+fn blame_sentence(log: &str, ctx: &EnvironmentContext, mod_id: &str, line: usize) -> String {
+    match suspect_mods(log, ctx, line).first() {
+        Some(top) if top.mod_id == mod_id => format!(
+            "`{mod_id}` is also the mod most present in the stack trace, so it's the most likely cause."
+        ),
+        Some(top) => format!(
+            "The stack trace mostly points at `{}` though, which may be the actual cause.",
+            top.mod_id
+        ),
+        None => "Further investigation is required.".to_string(),
+    }
+}
+
+pub fn dependency_generic(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
+    let missing = grab_every!(
+        log,
+        r"Mod '(.+?)' \(\S+\) \S+ requires any version between .+? and .+? of (\S+), which is missing!",
+        r"Mod '(.+?)' \(\S+\) \S+ requires version .+? or later of (\S+), which is missing!",
+        r"Mod '(.+?)' \(\S+\) \S+ requires any version of (\S+), which is missing!"
+    );
+    if let Some(first) = missing.first() {
+        let evidence = Some(Evidence::from_captures(log, first));
+        let groups = group_by_key(
+            missing
+                .iter()
+                .map(|c| (expect!(c, 1, MODID_SIZE), expect!(c, 2, MODID_SIZE))),
+        );
+        let mut dependencies: Vec<&String> = vec![];
+        for dep in groups.iter().flat_map(|(_, deps)| deps) {
+            if !dependencies.contains(&dep) {
+                dependencies.push(dep);
+            }
+        }
+        let actions = dependencies
+            .iter()
+            .take(3)
+            .map(|dep| search_modrinth(dep))
+            .collect();
+
+        if let [(dependent, deps)] = groups.as_slice()
+            && let [dependency] = deps.as_slice()
+        {
+            return Some(CheckReport {
+                id: "missing_dependency".to_string(),
+                title: "Missing dependency".to_string(),
+                description: format!(
+                    "The `{dependent}` mod needs `{dependency}` to be installed, but it is missing."
+                ),
+                severity: Severity::High,
+                evidence,
+                actions,
+            });
+        }
+
+        let mut description =
+            "The mods below need dependencies to be installed, but they are missing.\n".to_string();
+        write_list(
+            &mut description,
+            groups
+                .iter()
+                .map(|(dependent, deps)| format!("`{dependent}` needs {}", code_list(deps))),
+        );
+        return Some(CheckReport {
+            id: "missing_dependency".to_string(),
+            title: "Missing dependencies".to_string(),
+            description,
+            severity: Severity::High,
+            evidence,
+            actions,
         });
     }
 
@@ -131,57 +636,98 @@ pub fn dependency_generic(log: &str, _ctx: &EnvironmentContext) -> Option<CheckR
         let mod_implicated = expect!(captures, 2, MODID_SIZE);
         let implicated_version = expect!(captures, 3, 64);
         return Some(CheckReport {
+            id: "explicit_incompatibility".to_string(),
             title: "Explicit incompatibility".to_string(),
             description: format!(
                 "The `{mod_declared}` mod is incompatible with version `{implicated_version}` of the `{mod_implicated}` mod. Remove either mod, or try updating `{mod_implicated}` if possible."
             ),
             severity: Severity::High,
+            evidence: Some(Evidence::from_captures(log, &captures)),
+            actions: vec![],
         });
     }
 
     None
 }
 
-pub fn crash_generic(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
-    if peek!(log, r"java\.lang\.Error: Watchdog") {
+pub fn crash_generic(log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    if let Some(captures) = grab_all!(log, r"java\.lang\.Error: Watchdog") {
         return Some(CheckReport {
+            id: "watchdog".to_string(),
             title: "Watchdog crash".to_string(),
             description: "The server watchdog has killed the game. This usually happens when a tick takes way longer than its supposed to, which may be a result of extreme lag.".to_string(),
             severity: Severity::High,
+            evidence: Some(Evidence::from_captures(log, &captures)),
+            actions: vec![],
         });
     }
 
-    if peek!(log, r"java\.lang\.OutOfMemoryError:") {
+    if let Some(captures) = grab_all!(log, r"java\.lang\.OutOfMemoryError:") {
         return Some(CheckReport {
+            id: "out_of_memory".to_string(),
             title: "Out of memory".to_string(),
             description: "The game crashed because it ran out of memory. Consider allocating extra memory to the game or removing big content mods to save on memory usage.".to_string(),
             severity: Severity::High,
+            evidence: Some(Evidence::from_captures(log, &captures)),
+            actions: vec![],
         });
     }
 
-    if let Some(Some(mod_id)) = grab!(
+    if let Some(captures) = grab_all!(
         log,
-        64,
         r"RuntimeException: Error creating Mixin config \S+\.json for mod (\S+)"
     ) {
+        let mod_id = expect!(captures, 1, 64);
         return Some(CheckReport {
+            id: "invalid_mixin_config".to_string(),
             title: "Invalid mixin config".to_string(),
             description: format!("The mod `{mod_id}` is providing an invalid mixin config and cannot load in its current state, consider removing or updating it."),
             severity: Severity::High,
+            evidence: Some(Evidence::from_captures(log, &captures)),
+            actions: vec![],
         });
     }
 
-    if let Some(captures) = grab_all!(
+    let failed = grab_every!(
         log,
         r"InvalidInjectionException: Critical injection failure: @Inject annotation on \S+ could not find any targets matching '.+' in \S+\. Using refmap \S+ \[PREINJECT Applicator Phase \-> \S+:(\w+) from mod (\w+)",
         r"InvalidAccessorException: No candidates were found matching \S+ in \S+ for \S+:(\w+) from mod (\w+)"
-    ) {
-        let mixin = expect!(captures, 1, 128);
-        let mod_id = expect!(captures, 2, MODID_SIZE);
+    );
+    if let Some(first) = failed.first() {
+        let evidence = Some(Evidence::from_captures(log, first));
+        let groups = group_by_key(
+            failed
+                .iter()
+                .map(|c| (expect!(c, 2, MODID_SIZE), expect!(c, 1, 128))),
+        );
+
+        if let [(mod_id, mixins)] = groups.as_slice()
+            && let [mixin] = mixins.as_slice()
+        {
+            return Some(CheckReport {
+                id: "mixin_inject_failed".to_string(),
+                title: "Mixin inject failed".to_string(),
+                description: format!("Mixin `{mixin}` from mod `{mod_id}` has failed to apply. It is possible that `{mod_id}` is not compatible with this Minecraft version, consider double-checking its version."),
+                severity: Severity::High,
+                evidence,
+                actions: vec![],
+            });
+        }
+
+        let mut description = "The mixins below have failed to apply. It is possible that these mods are not compatible with this Minecraft version, consider double-checking their versions.\n".to_string();
+        write_list(
+            &mut description,
+            groups
+                .iter()
+                .map(|(mod_id, mixins)| format!("`{mod_id}`: {}", code_list(mixins))),
+        );
         return Some(CheckReport {
-            title: "Mixin inject failed".to_string(),
-            description: format!("Mixin `{mixin}` from mod `{mod_id}` has failed to apply. It is possible that `{mod_id}` is not compatible with this Minecraft version, consider double-checking its version."),
+            id: "mixin_inject_failed".to_string(),
+            title: "Mixin injects failed".to_string(),
+            description,
             severity: Severity::High,
+            evidence,
+            actions: vec![],
         });
     }
 
@@ -192,42 +738,55 @@ pub fn crash_generic(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport
         let mod1 = expect!(captures, 1, MODID_SIZE);
         let mod2 = expect!(captures, 2, MODID_SIZE);
         return Some(CheckReport {
+            id: "mixin_conflict".to_string(),
             title: "Mixin conflict".to_string(),
             description: format!("A mixin from the mod `{mod2}` collided with one from `{mod1}`, these mods may be incompatible."),
             severity: Severity::High,
+            evidence: Some(Evidence::from_captures(log, &captures)),
+            actions: vec![],
         });
     }
 
-    if let Some(Some(mod_id)) = grab!(
+    if let Some(captures) = grab_all!(
         log,
-        MODID_SIZE,
         r"MixinApplyError: Mixin \[\S+\.json:\S+ from mod (\S+)\] from phase \[\S+\] in config \[\S+\.json\] FAILED during \S+",
         r"InvalidInjectionException: .+ from mod ([\w\(\)-]+)\s?\->.+",
         r"Mixin apply for mod (\S+) failed \S+.json:\w+ from mod \S+ \-> \S+:"
     ) {
+        let mod_id = expect!(captures, 1, MODID_SIZE);
+        let evidence = Evidence::from_captures(log, &captures);
+        let blame = blame_sentence(log, ctx, &mod_id, evidence.line);
         return Some(CheckReport {
+            id: "mixin_error".to_string(),
             title: "Mixin error".to_string(),
-            description: format!("The mod `{mod_id}` has encountered a mixin error, this may be caused by a mismatch in Minecraft version or a mod incompatibility. Further investigation is required."),
+            description: format!("The mod `{mod_id}` has encountered a mixin error, this may be caused by a mismatch in Minecraft version or a mod incompatibility. {blame}"),
             severity: Severity::High,
+            evidence: Some(evidence),
+            actions: vec![],
         });
     }
 
-    if let Some(Some(mod_id)) = grab!(
+    if let Some(captures) = grab_all!(
         log,
-        MODID_SIZE,
         r"RuntimeException: Could not execute entrypoint stage '\S+' due to errors, provided by '(\S+)'!"
     ) {
+        let mod_id = expect!(captures, 1, MODID_SIZE);
+        let evidence = Evidence::from_captures(log, &captures);
+        let blame = blame_sentence(log, ctx, &mod_id, evidence.line);
         return Some(CheckReport {
+            id: "entrypoint_error".to_string(),
             title: "Entrypoint error".to_string(),
-            description: format!("The mod `{mod_id}` has encountered an error in it's entrypoint, though it may not have caused it. Further investigation is required."),
+            description: format!("The mod `{mod_id}` has encountered an error in it's entrypoint, though it may not have caused it. {blame}"),
             severity: Severity::High,
+            evidence: Some(evidence),
+            actions: vec![],
         });
     }
     None
 }
 
 pub fn mixin_conflicts(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
-    let regex_redirect = Regex::new(r"@Redirect conflict\. Skipping (?:#redirector:)?\S+\.json:\S+ from mod (\S+)\->@Redirect::\S+ with priority \w+, already redirected by \S+\.json:\S+ from mod (\S+)->@Redirect::\S+ with priority \w+").expect("Regex err");
+    let regex_redirect = static_regex!(r"@Redirect conflict\. Skipping (?:#redirector:)?\S+\.json:\S+ from mod (\S+)\->@Redirect::\S+ with priority \w+, already redirected by \S+\.json:\S+ from mod (\S+)->@Redirect::\S+ with priority \w+");
     let conflicts = regex_redirect
         .captures_iter(log)
         .take(7)
@@ -240,32 +799,60 @@ pub fn mixin_conflicts(log: &str, _ctx: &EnvironmentContext) -> Option<CheckRepo
         }
 
         return Some(CheckReport {
+            id: "mixin_redirect_conflicts".to_string(),
             title: "Mixin conflicts".to_string(),
             description,
             severity: Severity::Medium,
+            evidence: regex_redirect
+                .captures(log)
+                .map(|c| Evidence::from_captures(log, &c)),
+            actions: vec![],
         });
     }
     None
 }
 
-pub fn duck_fail(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
-    if let Some(Some(package)) = grab!(
-        log,
-        512,
-        r"AbstractMethodError: Receiver class \S+ does not define or inherit an implementation of the resolved method '.+' of interface (\S+)\.\w+\."
-    ) {
-        return Some(CheckReport {
-            title: "Duck interface failed".to_string(),
-            description: format!("A duck interface from the `{package}` package has failed to properly inject, this may indicate a broken mod or compatibility issue."),
-            severity: Severity::High,
-        });
-    }
-    None
+pub fn duck_fail(log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    let regex = static_regex!(
+        r"Receiver class \S+ does not define or inherit an implementation of the resolved method '.+' of interface (\S+)\.\w+\."
+    );
+    let found = ctx
+        .exceptions
+        .iter()
+        .flat_map(|e| e.all())
+        .filter(|e| e.is("java.lang.AbstractMethodError"))
+        .find_map(|e| Some((e, regex.captures(e.message?)?)));
+    let (package, evidence) = match found {
+        Some((exception, captures)) => (
+            expect!(captures, 1, 512),
+            Evidence::from_line(log, exception.line),
+        ),
+        // Traces the parser can't follow, like ones with log prefixes on every line
+        None => {
+            let captures = grab_all!(
+                log,
+                r"AbstractMethodError: Receiver class \S+ does not define or inherit an implementation of the resolved method '.+' of interface (\S+)\.\w+\."
+            )?;
+            (
+                expect!(captures, 1, 512),
+                Evidence::from_captures(log, &captures),
+            )
+        }
+    };
+
+    Some(CheckReport {
+        id: "duck_interface_failed".to_string(),
+        title: "Duck interface failed".to_string(),
+        description: format!("A duck interface from the `{package}` package has failed to properly inject, this may indicate a broken mod or compatibility issue."),
+        severity: Severity::High,
+        evidence: Some(evidence),
+        actions: vec![],
+    })
 }
 
 pub fn class_missing_generic(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
-    let packages = Regex::new(r"java\.lang\.ClassNotFoundException: (\S+)\.\w+")
-        .expect("Regex err")
+    let regex = static_regex!(r"java\.lang\.ClassNotFoundException: (\S+)\.\w+");
+    let packages = regex
         .captures_iter(log)
         .take(15)
         .map(|cap| expect!(cap, 1, 64))
@@ -277,17 +864,20 @@ pub fn class_missing_generic(log: &str, _ctx: &EnvironmentContext) -> Option<Che
         }
 
         return Some(CheckReport {
+            id: "missing_classes".to_string(),
             title: "Missing classes".to_string(),
             description,
             severity: Severity::Medium,
+            evidence: regex.captures(log).map(|c| Evidence::from_captures(log, &c)),
+            actions: vec![],
         });
     }
     None
 }
 
 pub fn broken_modmenu(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
-    let mods = Regex::new(r"Mod (\S+) provides a broken implementation of ModMenuApi")
-        .expect("Regex err")
+    let regex = static_regex!(r"Mod (\S+) provides a broken implementation of ModMenuApi");
+    let mods = regex
         .captures_iter(log)
         .take(15)
         .map(|cap| expect!(cap, 1, MODID_SIZE))
@@ -299,35 +889,47 @@ pub fn broken_modmenu(log: &str, _ctx: &EnvironmentContext) -> Option<CheckRepor
         }
 
         return Some(CheckReport {
+            id: "broken_config_screens".to_string(),
             title: "Broken config screens".to_string(),
             description,
             severity: Severity::Medium,
+            evidence: regex.captures(log).map(|c| Evidence::from_captures(log, &c)),
+            actions: vec![
+                CheckAction::new("Download Cloth Config", "https://modrinth.com/mod/cloth-config"),
+                CheckAction::new("Download YACL", "https://modrinth.com/mod/yacl"),
+            ],
         });
     }
     None
 }
 
 pub fn frozen_registry(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
-    if let Some(Some(namespace)) = grab!(
+    if let Some(captures) = grab_all!(
         log,
-        MODID_SIZE,
         r"IllegalStateException: Registry is already frozen \(trying to add key ResourceKey\[[\w-]+:[\w-]+ \/ ([\w-]+):\S+\]\)"
     ) {
+        let namespace = expect!(captures, 1, MODID_SIZE);
         return Some(CheckReport {
+            id: "frozen_registry".to_string(),
             title: "Frozen registry accessed".to_string(),
             description: format!("A mod with the `{namespace}` namespace tried to modify a frozen registry. This may indicate a broken mod or conflict."),
             severity: Severity::High,
+            evidence: Some(Evidence::from_captures(log, &captures)),
+            actions: vec![],
         });
     }
     None
 }
 
 pub fn failed_registry(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
-    if peek!(log, r"Failed to load registries due to above errors") {
+    if let Some(captures) = grab_all!(log, r"Failed to load registries due to above errors") {
         return Some(CheckReport {
+            id: "registry_failure".to_string(),
             title: "Critical registry failure".to_string(),
             description: "One or more registries experienced critical loading failures, this may be related to broken resource files.".to_string(),
             severity: Severity::High,
+            evidence: Some(Evidence::from_captures(log, &captures)),
+            actions: vec![],
         });
     }
     None
@@ -364,11 +966,14 @@ pub fn java(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
         let has = expect!(captures, 1, 16);
         let need = expect!(captures, 2, 16);
         return Some(CheckReport {
+            id: "java_version".to_string(),
             title: "Incorrect Java version".to_string(),
             description: format!(
                 "A mod or Minecraft itself requires Java {need} to be used, but an older version, Java {has} is being used instead. You may have to [download](https://adoptium.net/temurin/releases/?version={need}) a newer Java version and/or select it in your launcher."
             ),
             severity: Severity::High,
+            evidence: Some(Evidence::from_captures(log, &captures)),
+            actions: vec![download_java(Some(&need))],
         });
     }
     if let Some(captures) = grab_all!(
@@ -378,6 +983,7 @@ pub fn java(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
         let has = match_java_classfile_version(captures.get(2).expect("Regex err").as_str());
         let need = match_java_classfile_version(captures.get(1).expect("Regex err 2").as_str());
         return Some(CheckReport {
+            id: "java_version".to_string(),
             title: "Incorrect Java version".to_string(),
             description: if let Some(has) = has
                 && let Some(need) = need
@@ -389,77 +995,185 @@ pub fn java(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
                 "A mod or Minecraft itself requires a different version of Java from the one that is available. You may have to [download](https://adoptium.net/temurin/releases/) a newer Java version and/or select it in your launcher.".to_string()
             },
             severity: Severity::High,
+            evidence: Some(Evidence::from_captures(log, &captures)),
+            actions: vec![download_java(need)],
         });
     }
     None
 }
 
+/// Catches a Java version that's too old for the game itself, from the Java version the launcher
+/// printed, as the game can crash before Fabric gets to check it.
+pub fn game_java(log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    let game_version = ctx.game_version.as_ref()?;
+    let has = java_major_version(ctx.java_version.as_deref()?)?;
+    let need = game_version.java;
+    // Errors about the Java version are already reported by the check above
+    if has >= need
+        || peek!(
+            log,
+            r"- Replace '.+' \(java\) ([0-9]+) with version ([0-9]+) or later\.",
+            r"UnsupportedClassVersionError"
+        )
+    {
+        return None;
+    }
+
+    Some(CheckReport {
+        id: "java_version".to_string(),
+        title: "Incorrect Java version".to_string(),
+        description: format!(
+            "Minecraft {} requires Java {need} to be used, but an older version, Java {has} is being used instead. You may have to [download](https://adoptium.net/temurin/releases/?version={need}) a newer Java version and/or select it in your launcher.",
+            game_version.id
+        ),
+        severity: Severity::High,
+        evidence: None,
+        actions: vec![download_java(Some(&need.to_string()))],
+    })
+}
+
+pub fn snapshot(_log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    let game_version = ctx.game_version.as_ref()?;
+    if game_version.version_type != VersionType::Snapshot {
+        return None;
+    }
+
+    let mut description = format!(
+        "Minecraft {} is a snapshot, most mods don't support snapshots and may break in unexpected ways.",
+        game_version.id
+    );
+    if game_version.latest != game_version.id {
+        let _ = write!(
+            description,
+            " It has also been superseded by `{}`.",
+            game_version.latest
+        );
+    }
+    Some(CheckReport {
+        id: "snapshot".to_string(),
+        title: "Snapshot version".to_string(),
+        description,
+        severity: Severity::Medium,
+        evidence: None,
+        actions: vec![],
+    })
+}
+
+pub fn end_of_life(_log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    let game_version = ctx.game_version.as_ref()?;
+    // Old snapshots are reported as such instead
+    if !game_version.end_of_life || game_version.version_type == VersionType::Snapshot {
+        return None;
+    }
+
+    Some(CheckReport {
+        id: "end_of_life".to_string(),
+        title: "Old Minecraft version".to_string(),
+        description: format!(
+            "Minecraft {} was released in {}, most mods no longer receive updates or fixes for it. Support for it may be limited.",
+            game_version.id,
+            &game_version.release_time[..4]
+        ),
+        severity: Severity::None,
+        evidence: None,
+        actions: vec![],
+    })
+}
+
 pub fn jdk(log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
-    if peek!(
+    if let Some(captures) = grab_all!(
         log,
         r"IllegalStateException: No compatible attachment provider is available"
     ) {
+        let java = ctx
+            .java_version
+            .as_deref()
+            .and_then(java_major_version)
+            .map(|major| major.to_string());
         return Some(CheckReport {
+            id: "jre_instead_of_jdk".to_string(),
             title: "JRE used instead of JDK".to_string(),
-            description: if let Some(java) = ctx.discovered_mods.iter().find(|m| m.0 == "java") {
+            description: if let Some(java) = &java {
                 format!(
-                    "A mod or Minecraft itself requires the use of a JDK type distribution of Java instead of the used JRE type. You may have to [download](https://adoptium.net/temurin/releases/?version={}) a JDK type Java version and/or select it in your launcher.",
-                    java.1
+                    "A mod or Minecraft itself requires the use of a JDK type distribution of Java instead of the used JRE type. You may have to [download](https://adoptium.net/temurin/releases/?version={java}) a JDK type Java version and/or select it in your launcher."
                 )
             } else {
                 "A mod or Minecraft itself requires the use of a JDK type distribution of Java instead of the used JRE type. You may have to [download](https://adoptium.net/temurin/releases/) a JDK type Java version and/or select it in your launcher.".to_string()
             },
             severity: Severity::High,
+            evidence: Some(Evidence::from_captures(log, &captures)),
+            actions: vec![download_java(java.as_deref())],
         });
     }
     None
 }
 
 pub fn broken_cicada_config(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
-    if let Some(Some(path)) = grab!(
+    if let Some(captures) = grab_all!(
         log,
-        512,
         r"\[cicada\] Failed to parse config file, backing up and overwriting with default config: (.+)"
     ) {
+        let path = expect!(captures, 1, 512);
         return Some(CheckReport {
+            id: "cicada_broken_config".to_string(),
             title: "Broken config file".to_string(),
             description: format!("The config file below has failed to load. It has been backed up and reverted to its original state.\n```{path}```"),
             severity: Severity::Medium,
+            evidence: Some(Evidence::from_captures(log, &captures)),
+            actions: vec![],
         });
     }
     None
 }
 
-pub fn missing_field(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
-    if peek!(log, r"java\.lang\.NoSuchFieldError") {
-        return Some(CheckReport {
-            title: "Field missing error".to_string(),
-            description: "On the logical server some fields may be deleted by Fabric Loader when a mod defines them as client-only. Since this feature was broken before loader `0.15`, some mods may have implemented it incorrectly. See if there's an update for the mod in question, or try downgrading Fabric Loader.".to_string(),
-            severity: Severity::High,
-        });
+pub fn missing_field(log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    let exception = ctx
+        .exceptions
+        .iter()
+        .find_map(|e| e.chain().filter(|e| e.is("java.lang.NoSuchFieldError")).last())?;
+
+    let mut description = String::new();
+    if let Some(field) = exception.summary() {
+        let _ = write!(description, "Field `{}` is missing", truncate!(field.trim().to_string(), 128));
+        if let Some(frame) = exception.frames.first() {
+            let _ = write!(description, ", accessed from `{}`", truncate!(frame.class_name().to_string(), 256));
+        }
+        description.push_str(". ");
     }
-    None
+    description.push_str("On the logical server some fields may be deleted by Fabric Loader when a mod defines them as client-only. Since this feature was broken before loader `0.15`, some mods may have implemented it incorrectly. See if there's an update for the mod in question, or try downgrading Fabric Loader.");
+
+    Some(CheckReport {
+        id: "missing_field".to_string(),
+        title: "Field missing error".to_string(),
+        description,
+        severity: Severity::High,
+        evidence: Some(Evidence::from_line(log, exception.line)),
+        actions: vec![],
+    })
 }
 
 pub fn datapacks_failed(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
-    if peek!(
+    if let Some(captures) = grab_all!(
         log,
         r"Failed to load datapacks, can't proceed with server load\. You can either fix your datapacks or reset to vanilla"
     ) {
         return Some(CheckReport {
+            id: "datapacks_failed".to_string(),
             title: "Datapack loading failed".to_string(),
             description:
                 "The server couldn't load datapack resources, further investigation is required."
                     .to_string(),
             severity: Severity::High,
+            evidence: Some(Evidence::from_captures(log, &captures)),
+            actions: vec![],
         });
     }
     None
 }
 
 pub fn resource_files(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
-    let ids = Regex::new(r"Failed to parse (\S+) from pack [\w:-]")
-        .expect("Regex err")
+    let regex = static_regex!(r"Failed to parse (\S+) from pack [\w:-]");
+    let ids = regex
         .captures_iter(log)
         .take(14)
         .map(|cap| expect!(cap, 1, 64))
@@ -471,32 +1185,159 @@ pub fn resource_files(log: &str, _ctx: &EnvironmentContext) -> Option<CheckRepor
         }
 
         return Some(CheckReport {
+            id: "broken_resource_files".to_string(),
             title: "Broken resource files".to_string(),
             description,
             severity: Severity::Medium,
+            evidence: regex.captures(log).map(|c| Evidence::from_captures(log, &c)),
+            actions: vec![],
         });
     }
     None
 }
 
 pub fn disk_full(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
-    if peek!(log, r"IOException: No space left on device") {
+    if let Some(captures) = grab_all!(log, r"IOException: No space left on device") {
         return Some(CheckReport {
+            id: "disk_full".to_string(),
             title: "Full storage".to_string(),
             description: "The game cannot save certain data, your storage drive might be full."
                 .to_string(),
             severity: Severity::High,
+            evidence: Some(Evidence::from_captures(log, &captures)),
+            actions: vec![],
         });
     }
     None
 }
 
+pub fn low_memory(_log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    let max_memory = ctx.system.as_ref()?.max_memory?;
+    // Bundled mods are mostly small libraries, so only the ones installed directly are counted
+    let mods = ctx
+        .discovered_mods
+        .iter()
+        .filter(|m| !m.builtin && m.parent.is_none())
+        .count() as u64;
+    let recommended = BASE_MEMORY + mods * MEMORY_PER_MOD;
+    if max_memory >= recommended {
+        return None;
+    }
+
+    Some(CheckReport {
+        id: "low_memory".to_string(),
+        title: "Low allocated memory".to_string(),
+        description: format!(
+            "Only {max_memory} MiB of memory is allocated to the game, which may not be enough for {mods} mods. Consider allocating at least {} GB in your launcher's settings.",
+            recommended.div_ceil(1024)
+        ),
+        severity: Severity::Medium,
+        evidence: None,
+        actions: vec![],
+    })
+}
+
+pub fn software_renderer(_log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    let gpu = ctx.system.as_ref()?.gpu.as_ref()?;
+    if !gpu.is_software() {
+        return None;
+    }
+
+    Some(CheckReport {
+        id: "software_renderer".to_string(),
+        title: "No graphics drivers".to_string(),
+        description: format!(
+            "The game is being rendered by `{}`, which means the graphics drivers are missing or broken. Install the latest drivers from the website of your graphics card's manufacturer.",
+            gpu.renderer
+        ),
+        severity: Severity::High,
+        evidence: None,
+        actions: vec![],
+    })
+}
+
+pub fn outdated_gpu_driver(_log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    let gpu = ctx.system.as_ref()?.gpu.as_ref()?;
+    if !gpu.outdated_driver() {
+        return None;
+    }
+
+    let action = if gpu.vendor.contains("Intel") {
+        CheckAction::new(
+            "Intel driver assistant",
+            "https://www.intel.com/content/www/us/en/support/detect.html",
+        )
+    } else {
+        CheckAction::new(
+            "AMD drivers",
+            "https://www.amd.com/en/support/download/drivers.html",
+        )
+    };
+    Some(CheckReport {
+        id: "outdated_gpu_driver".to_string(),
+        title: "Outdated graphics drivers".to_string(),
+        description: format!(
+            "The drivers for `{}` are outdated (`{}`), old drivers are known to cause crashes and rendering issues. Consider updating them.",
+            gpu.renderer, gpu.gl_version
+        ),
+        severity: Severity::Medium,
+        evidence: None,
+        actions: vec![action],
+    })
+}
+
+pub fn resource_packs(_log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    let packs = ctx.system.as_ref()?.user_resource_packs().count();
+    if packs <= RESOURCE_PACK_LIMIT {
+        return None;
+    }
+
+    Some(CheckReport {
+        id: "resource_packs".to_string(),
+        title: "Many resource packs".to_string(),
+        description: format!(
+            "{packs} resource packs are enabled, which slows down loading and uses a lot of memory. If the game is struggling, try disabling the ones you don't need."
+        ),
+        severity: Severity::Medium,
+        evidence: None,
+        actions: vec![],
+    })
+}
+
+pub fn first_error(log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    let record = ctx.records.iter().find(|r| r.level >= Level::Error)?;
+
+    let mut description = format!("`[{}/{}]`", record.thread, record.level);
+    if let Some(logger) = record.logger {
+        let _ = write!(description, " from `{logger}`");
+    }
+    let message = record
+        .message
+        .lines()
+        .take(FIRST_ERROR_LINES)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let _ = write!(description, "\n```\n{}\n```", truncate!(message, 768));
+
+    Some(CheckReport {
+        id: "first_error".to_string(),
+        title: "First error".to_string(),
+        description,
+        severity: Severity::None,
+        evidence: Some(Evidence::from_line(log, record.line)),
+        actions: vec![],
+    })
+}
+
 pub fn quilt(_log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
     if let Some(ModLoader::Quilt(_)) = &ctx.loader {
         return Some(CheckReport {
+            id: "quilt".to_string(),
             title: "Quilt detected".to_string(),
             description: "Many mod developers may not officially support Quilt. Consider switching to Fabric if you aren't using any Quilt-specific mods.".to_string(),
             severity: Severity::None,
+            evidence: None,
+            actions: vec![],
         });
     }
     None
@@ -505,57 +1346,32 @@ pub fn quilt(_log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
 pub fn polymc(_log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
     if let Some(Launcher::PolyMC) = &ctx.launcher {
         return Some(CheckReport {
+            id: "polymc".to_string(),
             title: "PolyMC detected".to_string(),
             description: "PolyMC is an outdated launcher maintained by a queerphobic team. Consider switching to [Prism Launcher](https://prismlauncher.org/), a fork with more features and better support.".to_string(),
             severity: Severity::Medium,
+            evidence: None,
+            actions: vec![CheckAction::new(
+                "Download Prism Launcher",
+                "https://prismlauncher.org/download/",
+            )],
         });
     }
     None
 }
 
-pub fn optifabric(log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
-    if ctx
-        .known_mods
-        .iter()
-        .find(|m| m.0 .0 == "optifabric")
-        .is_some()
-        || peek!(
-            log,
-            r"Mod '.+' \(\S+\) \S+ is incompatible with any version of mod '.+' \(optifabric\)",
-            r"me\.modmuss50\.optifabric"
-        )
-    {
+pub fn tlauncher(_log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+    if let Some(Launcher::TLauncher) = &ctx.launcher {
         return Some(CheckReport {
-            title: "OptiFabric detected".to_string(),
-            description: "Optifine is known to cause problems with many mods on Fabric. If you're having strange issues or crashes, consider replacing it with some of the many available [alternatives](https://lambdaurora.dev/optifine_alternatives/).".to_string(),
+            id: "tlauncher".to_string(),
+            title: "TLauncher detected".to_string(),
+            description: "TLauncher is a launcher for pirated copies of the game, and has a history of bundling spyware. We can't help with issues on cracked launchers. Buy the game and use a trustworthy launcher like [Prism Launcher](https://prismlauncher.org/) instead, and consider scanning your computer for malware.".to_string(),
             severity: Severity::High,
-        });
-    }
-    None
-}
-
-pub fn bclib(_log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
-    if ctx.known_mods.iter().find(|m| m.0 .0 == "bclib").is_some() {
-        return Some(CheckReport {
-            title: "BCLib detected".to_string(),
-            description: "BCLib is known to cause issues with some mods. If you're experiencing crashes or other problems, consider trying without it.".to_string(),
-            severity: Severity::Medium,
-        });
-    }
-    None
-}
-
-pub fn feather(_log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
-    if ctx
-        .known_mods
-        .iter()
-        .find(|m| m.0 .0 == "feather")
-        .is_some()
-    {
-        return Some(CheckReport {
-            title: "Feather Client detected".to_string(),
-            description: "Feather Client is known to cause issues with some mods. If you're experiencing crashes or other problems, consider trying without it.".to_string(),
-            severity: Severity::Medium,
+            evidence: None,
+            actions: vec![CheckAction::new(
+                "Download Prism Launcher",
+                "https://prismlauncher.org/download/",
+            )],
         });
     }
     None
@@ -574,25 +1390,32 @@ pub fn feather(_log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
 // }
 
 pub fn mcreator(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
-    if let Some(Some(mod_id)) = grab!(log, MODID_SIZE, r"at net\.mcreator\.([\w-]+)\.") {
+    if let Some(captures) = grab_all!(log, r"at net\.mcreator\.([\w-]+)\.") {
+        let mod_id = expect!(captures, 1, MODID_SIZE);
         return Some(CheckReport {
+            id: "mcreator".to_string(),
             title: "MCreator mod issue".to_string(),
             description: format!("The mod `{mod_id}` is being mentioned in an error message. This is a mod made using MCreator, a tool for easily making basic mods.\n\nMCreator is known to produce subpar code that might cause issues with other mods. Consider removing this mod to alleviate potential issues."),
             severity: Severity::Medium,
+            evidence: Some(Evidence::from_captures(log, &captures)),
+            actions: vec![],
         });
     }
     None
 }
 
 pub fn indium(log: &str, _ctx: &EnvironmentContext) -> Option<CheckReport> {
-    if peek!(
+    if let Some(captures) = grab_all!(
         log,
         r#"because the return value of "net\.fabricmc\.fabric\.api\.renderer\.v1\.RendererAccess\.getRenderer\(\)" is null"#
     ) {
         return Some(CheckReport {
+            id: "missing_indium".to_string(),
             title: "Missing Indium".to_string(),
             description: "A mod is trying to make use of Fabric Rendering API, which may be missing when rendering mods such as Sodium are loaded. If you use Sodium, install [Indium](https://modrinth.com/mod/indium) to resolve this.".to_string(),
             severity: Severity::High,
+            evidence: Some(Evidence::from_captures(log, &captures)),
+            actions: vec![CheckAction::new("Download Indium", "https://modrinth.com/mod/indium")],
         });
     }
     None
@@ -0,0 +1,156 @@
+use std::{fs, path::Path};
+
+use regex::{Captures, Regex};
+use serde::Deserialize;
+
+use crate::{static_regex, truncate};
+
+use super::{
+    checks::{CheckAction, CheckReport, Evidence, Severity},
+    environment::{EnvironmentContext, DESCRIBED_PROPERTIES},
+};
+
+#[derive(Deserialize)]
+struct CheckDefinition {
+    regexes: Vec<String>,
+    severity: Severity,
+    title: String,
+    response: String,
+    #[serde(default)]
+    actions: Vec<CheckAction>,
+    #[serde(default)]
+    covered_by: Vec<String>,
+}
+
+/// A check loaded from a `log_checks/*.toml` file, matching any of its regexes
+/// and filling the named captures into its response template.
+#[derive(Clone)]
+pub struct DeclarativeCheck {
+    pub name: String,
+    regexes: Vec<Regex>,
+    severity: Severity,
+    title: String,
+    response: String,
+    actions: Vec<CheckAction>,
+    /// Ids of built-in checks that report the same problem, or parts of the environment summary
+    /// that show the same thing. This check is left out when one of them already has.
+    covered_by: Vec<String>,
+}
+
+/// Reads every check in `./log_checks/`, skipping the ones that fail to load and returning
+/// their errors along with the check's name.
+pub fn read_declarative_checks() -> (Vec<DeclarativeCheck>, Vec<(String, String)>) {
+    let files = match fs::read_dir("./log_checks/") {
+        Ok(files) => files,
+        Err(err) => return (vec![], vec![("log_checks".to_string(), err.to_string())]),
+    };
+
+    let mut paths = files
+        .filter_map(|file| file.ok().map(|file| file.path()))
+        .filter(|path| {
+            path.extension()
+                .map_or(false, |ext| ext.eq_ignore_ascii_case("toml"))
+        })
+        .collect::<Vec<_>>();
+    // Files are prefixed with a number to control the order of the reports
+    paths.sort();
+
+    let mut checks = vec![];
+    let mut errors = vec![];
+    for path in paths {
+        let loaded = fs::read_to_string(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|s| Ok(toml::from_str::<CheckDefinition>(&s)?))
+            .and_then(|definition| DeclarativeCheck::compile(&path, definition));
+        match loaded {
+            Ok(check) => checks.push(check),
+            Err(err) => errors.push((check_name(&path), err.to_string())),
+        }
+    }
+    (checks, errors)
+}
+
+/// Like [`read_declarative_checks`], but panics if any check fails to load.
+pub fn load_declarative_checks() -> Vec<DeclarativeCheck> {
+    let (checks, errors) = read_declarative_checks();
+    if let Some((name, err)) = errors.first() {
+        panic!("loading log check {name}: {err}");
+    }
+    checks
+}
+
+fn check_name(path: &Path) -> String {
+    path.file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+impl DeclarativeCheck {
+    fn compile(path: &Path, definition: CheckDefinition) -> anyhow::Result<Self> {
+        if let Some(unknown) = definition.covered_by.iter().find(|id| {
+            id.starts_with("environment.") && !DESCRIBED_PROPERTIES.contains(&id.as_str())
+        }) {
+            return Err(anyhow::anyhow!(
+                "{unknown} isn't part of the environment summary, expected one of {}",
+                DESCRIBED_PROPERTIES.join(", ")
+            ));
+        }
+        Ok(DeclarativeCheck {
+            name: check_name(path),
+            regexes: definition
+                .regexes
+                .iter()
+                .map(|r| Regex::new(r))
+                .collect::<Result<_, _>>()?,
+            severity: definition.severity,
+            title: definition.title,
+            response: definition.response,
+            actions: definition.actions,
+            covered_by: definition.covered_by,
+        })
+    }
+
+    pub fn check(
+        &self,
+        log: &str,
+        ctx: &EnvironmentContext,
+        reports: &[CheckReport],
+    ) -> Option<CheckReport> {
+        if self
+            .covered_by
+            .iter()
+            .any(|id| ctx.describes(id) || reports.iter().any(|r| &r.id == id))
+        {
+            return None;
+        }
+        let captures = self.regexes.iter().find_map(|r| r.captures(log))?;
+
+        Some(CheckReport {
+            id: self.name.clone(),
+            title: self.title.clone(),
+            description: render(&self.response, &captures),
+            severity: self.severity,
+            evidence: Some(Evidence::from_captures(log, &captures)),
+            actions: self
+                .actions
+                .iter()
+                .map(|action| CheckAction {
+                    label: render(&action.label, &captures),
+                    url: render(&action.url, &captures),
+                })
+                .collect(),
+        })
+    }
+}
+
+fn render(template: &str, captures: &Captures) -> String {
+    static_regex!(r"\{(\w+)\}")
+        .replace_all(template, |caps: &Captures| {
+            let name = caps.get(1).expect("Regex err").as_str();
+            captures
+                .name(name)
+                .map(|m| truncate!(m.as_str().trim().to_string(), 128))
+                .unwrap_or_else(|| caps.get(0).expect("Regex err").as_str().to_string())
+        })
+        .into_owned()
+}
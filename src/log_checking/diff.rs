@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+
+use super::{
+    checks::{CheckReport, Severity},
+    environment::EnvironmentContext,
+};
+
+/// What changed between two logs, usually one from when the game still worked and one from after
+/// it broke.
+pub struct LogDiff<'a> {
+    /// Name of each property that changed, along with its old and new value.
+    pub environment: Vec<(&'static str, String, String)>,
+    /// Ids of mods along with their versions.
+    pub added_mods: Vec<(&'a str, &'a str)>,
+    pub removed_mods: Vec<(&'a str, &'a str)>,
+    /// Ids of mods along with their old and new version.
+    pub updated_mods: Vec<(&'a str, &'a str, &'a str)>,
+    /// Checks that only fire for the new log.
+    pub new_checks: Vec<(Severity, String)>,
+    /// Checks that only fire for the old log.
+    pub resolved_checks: Vec<(Severity, String)>,
+}
+
+fn environment(ctx: &EnvironmentContext) -> [(&'static str, Option<String>); 5] {
+    [
+        ("Minecraft", ctx.mc_version.clone()),
+        (
+            "Loader",
+            ctx.loader.as_ref().map(|l| match l.version() {
+                Some(version) => format!("{} {version}", l.name()),
+                None => l.name().to_string(),
+            }),
+        ),
+        ("Java", ctx.java_version.clone()),
+        (
+            "Launcher",
+            ctx.launcher.as_ref().map(|l| l.name().to_string()),
+        ),
+        ("System", ctx.os.clone()),
+    ]
+}
+
+/// Installed mods by id, leaving out the game, the loader and Java as they're compared separately.
+fn mods<'a>(ctx: &EnvironmentContext<'a>) -> HashMap<&'a str, &'a str> {
+    ctx.discovered_mods
+        .iter()
+        .filter(|m| !m.builtin)
+        .map(|m| (m.id, m.version))
+        .collect()
+}
+
+fn checks_missing_from(checks: &[CheckReport], other: &[CheckReport]) -> Vec<(Severity, String)> {
+    checks
+        .iter()
+        .filter(|c| !other.iter().any(|o| o.id == c.id))
+        .map(|c| (c.severity, c.title.clone()))
+        .collect()
+}
+
+impl<'a> LogDiff<'a> {
+    pub fn new(
+        (old, old_checks): (&EnvironmentContext<'a>, &[CheckReport]),
+        (new, new_checks): (&EnvironmentContext<'a>, &[CheckReport]),
+    ) -> LogDiff<'a> {
+        let environment = environment(old)
+            .into_iter()
+            .zip(environment(new))
+            .filter(|((_, a), (_, b))| a != b)
+            .map(|((name, a), (_, b))| {
+                let unknown = || "unknown".to_string();
+                (name, a.unwrap_or_else(unknown), b.unwrap_or_else(unknown))
+            })
+            .collect();
+
+        let old_mods = mods(old);
+        let new_mods = mods(new);
+        let mut added_mods = new_mods
+            .iter()
+            .filter(|(id, _)| !old_mods.contains_key(*id))
+            .map(|(id, version)| (*id, *version))
+            .collect::<Vec<_>>();
+        let mut removed_mods = old_mods
+            .iter()
+            .filter(|(id, _)| !new_mods.contains_key(*id))
+            .map(|(id, version)| (*id, *version))
+            .collect::<Vec<_>>();
+        let mut updated_mods = new_mods
+            .iter()
+            .filter_map(|(id, version)| {
+                let old_version = old_mods.get(id).filter(|v| *v != version)?;
+                Some((*id, *old_version, *version))
+            })
+            .collect::<Vec<_>>();
+        added_mods.sort();
+        removed_mods.sort();
+        updated_mods.sort();
+
+        LogDiff {
+            environment,
+            added_mods,
+            removed_mods,
+            updated_mods,
+            new_checks: checks_missing_from(new_checks, old_checks),
+            resolved_checks: checks_missing_from(old_checks, new_checks),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.environment.is_empty()
+            && self.added_mods.is_empty()
+            && self.removed_mods.is_empty()
+            && self.updated_mods.is_empty()
+            && self.new_checks.is_empty()
+            && self.resolved_checks.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LogDiff;
+    use crate::{
+        log_checking::{
+            checks::{CheckReport, Severity},
+            environment::get_environment_info,
+        },
+        log_upload::MapStatus,
+    };
+
+    const OLD: &str = "Prism Launcher version: 8.3 (official)\n\
+[10:00:00] [main/INFO]: Loading Minecraft 1.20.1 with Fabric Loader 0.15.7\n\
+[10:00:00] [main/INFO]: Loading 5 mods:\n\
+\t- fabric-api 0.92.0+1.20.1\n\
+\t- fabricloader 0.15.7\n\
+\t- java 17\n\
+\t- minecraft 1.20.1\n\
+\t- sodium 0.5.8+mc1.20.1\n";
+
+    const NEW: &str = "Prism Launcher version: 8.3 (official)\n\
+[11:00:00] [main/INFO]: Loading Minecraft 1.20.1 with Fabric Loader 0.15.11\n\
+[11:00:00] [main/INFO]: Loading 5 mods:\n\
+\t- fabric-api 0.92.2+1.20.1\n\
+\t- fabricloader 0.15.11\n\
+\t- iris 1.7.0+mc1.20.1\n\
+\t- java 17\n\
+\t- minecraft 1.20.1\n";
+
+    fn report(id: &str, severity: Severity) -> CheckReport {
+        CheckReport {
+            id: id.to_string(),
+            title: id.to_string(),
+            description: String::new(),
+            severity,
+            evidence: None,
+            actions: vec![],
+        }
+    }
+
+    #[test]
+    fn mods_and_environment() {
+        let old = get_environment_info(OLD, &MapStatus::NotRequired);
+        let new = get_environment_info(NEW, &MapStatus::NotRequired);
+        let diff = LogDiff::new((&old, &[]), (&new, &[]));
+
+        assert_eq!(diff.added_mods, [("iris", "1.7.0+mc1.20.1")]);
+        assert_eq!(diff.removed_mods, [("sodium", "0.5.8+mc1.20.1")]);
+        assert_eq!(
+            diff.updated_mods,
+            [("fabric-api", "0.92.0+1.20.1", "0.92.2+1.20.1")]
+        );
+        assert_eq!(
+            diff.environment,
+            [(
+                "Loader",
+                "Fabric 0.15.7".to_string(),
+                "Fabric 0.15.11".to_string()
+            )]
+        );
+        assert!(!diff.is_empty());
+    }
+
+    #[test]
+    fn new_and_resolved_checks() {
+        let old = get_environment_info(OLD, &MapStatus::NotRequired);
+        let new = get_environment_info(OLD, &MapStatus::NotRequired);
+        let old_checks = [
+            report("kept", Severity::Medium),
+            report("fixed", Severity::High),
+        ];
+        let new_checks = [
+            report("kept", Severity::High),
+            report("broken", Severity::High),
+        ];
+        let diff = LogDiff::new((&old, &old_checks), (&new, &new_checks));
+
+        assert_eq!(diff.new_checks, [(Severity::High, "broken".to_string())]);
+        assert_eq!(
+            diff.resolved_checks,
+            [(Severity::High, "fixed".to_string())]
+        );
+        assert!(diff.environment.is_empty());
+        assert!(diff.added_mods.is_empty() && diff.removed_mods.is_empty());
+        assert!(diff.updated_mods.is_empty());
+    }
+
+    #[test]
+    fn identical_logs() {
+        let old = get_environment_info(NEW, &MapStatus::NotRequired);
+        let new = get_environment_info(NEW, &MapStatus::NotRequired);
+        assert!(LogDiff::new((&old, &[]), (&new, &[])).is_empty());
+    }
+}
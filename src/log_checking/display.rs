@@ -0,0 +1,118 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+};
+
+use anyhow::Result;
+use serde::Deserialize;
+
+use super::{
+    checks::Severity,
+    environment::{Launcher, ModLoader},
+    mods::DiscoveredMod,
+};
+
+/// A custom Discord emoji, along with a Unicode fallback for when it can't be used.
+#[derive(Deserialize, Clone, Default)]
+pub struct Icon {
+    pub emoji: Option<String>,
+    pub fallback: Option<String>,
+}
+
+/// A mod that gets called out in the embed whenever it's installed.
+#[derive(Deserialize, Clone)]
+pub struct HighlightedMod {
+    pub id: String,
+    pub name: String,
+    #[serde(flatten)]
+    pub icon: Icon,
+    pub homepage: Option<String>,
+}
+
+/// How log check results are presented, loaded from `display.toml`.
+#[derive(Deserialize, Clone, Default)]
+pub struct DisplayData {
+    /// Whether custom emoji can be used, or only their fallbacks.
+    #[serde(skip)]
+    pub custom_emoji: bool,
+    #[serde(default)]
+    severities: HashMap<Severity, Icon>,
+    /// Keyed by [`ModLoader::name`].
+    #[serde(default)]
+    loaders: HashMap<String, Icon>,
+    /// Keyed by [`Launcher::name`].
+    #[serde(default)]
+    launchers: HashMap<String, Icon>,
+    #[serde(default)]
+    mods: Vec<HighlightedMod>,
+}
+
+pub fn load_display_data(custom_emoji: bool) -> Result<DisplayData> {
+    let mut data = toml::from_str::<DisplayData>(&fs::read_to_string("./display.toml")?)?;
+    data.custom_emoji = custom_emoji;
+    Ok(data)
+}
+
+impl DisplayData {
+    fn icon<'a>(&self, icon: Option<&'a Icon>) -> Option<&'a str> {
+        let icon = icon?;
+        if self.custom_emoji {
+            icon.emoji.as_deref().or(icon.fallback.as_deref())
+        } else {
+            icon.fallback.as_deref()
+        }
+    }
+
+    pub fn severity(&self, severity: Severity) -> &str {
+        self.icon(self.severities.get(&severity))
+            .unwrap_or(match severity {
+                Severity::None => "🔵",
+                Severity::Medium => "🟠",
+                Severity::High => "🔴",
+            })
+    }
+
+    pub fn loader(&self, loader: &ModLoader) -> String {
+        let name = self
+            .icon(self.loaders.get(loader.name()))
+            .unwrap_or(loader.name());
+        match loader.version() {
+            Some(version) => format!("{name} `{version}`"),
+            None => name.to_string(),
+        }
+    }
+
+    pub fn launcher(&self, launcher: &Launcher) -> String {
+        self.icon(self.launchers.get(launcher.name()))
+            .unwrap_or(launcher.name())
+            .to_string()
+    }
+
+    /// How a highlighted mod is listed, like `<:owo:...> [oωo](https://...)`.
+    pub fn highlighted_mod(&self, highlighted: &HighlightedMod) -> String {
+        let name = match &highlighted.homepage {
+            Some(homepage) => format!("[{}]({homepage})", highlighted.name),
+            None => highlighted.name.clone(),
+        };
+        match self.icon(Some(&highlighted.icon)) {
+            Some(icon) => format!("{icon} {name}"),
+            None => name,
+        }
+    }
+
+    /// The highlighted mods that are installed, in the order they're configured.
+    pub fn highlighted_mods<'b, 'c>(
+        &self,
+        discovered_mods: &'b HashSet<DiscoveredMod<'c>>,
+    ) -> Vec<(&HighlightedMod, &'b DiscoveredMod<'c>)> {
+        self.mods
+            .iter()
+            .filter_map(|highlighted| {
+                discovered_mods
+                    .iter()
+                    .find(|m| m.id == highlighted.id)
+                    .map(|m| (highlighted, m))
+            })
+            .collect()
+    }
+}
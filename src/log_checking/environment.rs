@@ -1,34 +1,44 @@
-use std::{collections::HashSet, fmt::Display, hash::Hash};
-
-use regex::Regex;
+use std::{collections::HashSet, fmt::Write};
 
 use crate::log_upload::MapStatus;
 
+use super::{
+    display::DisplayData,
+    minecraft::GameVersion,
+    mods::{parse_mod_list, DiscoveredMod},
+    records::{parse_records, Level, LogRecord},
+    stacktrace::{parse_exceptions, JavaException},
+    system::{parse_system_details, SystemDetails},
+    updates::ModUpdate,
+};
+
 pub enum ModLoader {
     Fabric(Option<String>),
-    Forge,
-    NeoForge,
+    Forge(Option<String>),
+    NeoForge(Option<String>),
     Quilt(Option<String>),
 }
 
-impl Display for ModLoader {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl ModLoader {
+    pub fn name(&self) -> &'static str {
         match self {
-            Self::Fabric(Some(version)) => {
-                write!(f, "<:fabric:1246103308842700831> `{version}`")
-            }
-            Self::Fabric(None) => write!(f, "<:fabric:1246103308842700831>"),
-            Self::Forge => write!(f, "<:forge:1246170624364380221>"),
-            Self::NeoForge => write!(f, "<:neoforge:1246170626159415326>"),
-            Self::Quilt(Some(version)) => {
-                write!(f, "<:quilt:1246170627652718653> `{version}`")
-            }
-            Self::Quilt(None) => write!(f, "<:quilt:1246170627652718653>"),
+            Self::Fabric(_) => "Fabric",
+            Self::Forge(_) => "Forge",
+            Self::NeoForge(_) => "NeoForge",
+            Self::Quilt(_) => "Quilt",
+        }
+    }
+
+    pub fn version(&self) -> Option<&str> {
+        match self {
+            Self::Fabric(version)
+            | Self::Forge(version)
+            | Self::NeoForge(version)
+            | Self::Quilt(version) => version.as_deref(),
         }
     }
 }
 
-#[allow(dead_code)]
 #[derive(PartialEq)]
 pub enum Launcher {
     Prism,
@@ -37,77 +47,150 @@ pub enum Launcher {
     Vanilla,
     CurseForge,
     Theseus, // Modrinth launcher
+    ATLauncher,
+    GDLauncher,
+    Technic,
+    Lunar,
+    SKLauncher,
+    TLauncher,
 }
 
-impl Display for Launcher {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl Launcher {
+    pub fn name(&self) -> &'static str {
         match self {
-            Self::Prism => write!(f, "<:prism:1246451647677468714>"),
-            Self::PolyMC => write!(f, "<:polymc:1246451649212448860>"),
-            Self::MultiMC => write!(f, "<:multimc:1246451644342865992>"),
-            Self::Vanilla => write!(f, "<:minecraft:1246451645441642496>"),
-            Self::CurseForge => write!(f, "<:curseforge:1246451646909911141>"),
-            Self::Theseus => write!(f, "<:modrinth:1255163992658149376>"),
+            Self::Prism => "Prism Launcher",
+            Self::PolyMC => "PolyMC",
+            Self::MultiMC => "MultiMC",
+            Self::Vanilla => "Minecraft Launcher",
+            Self::CurseForge => "CurseForge",
+            Self::Theseus => "Modrinth App",
+            Self::ATLauncher => "ATLauncher",
+            Self::GDLauncher => "GDLauncher",
+            Self::Technic => "Technic Launcher",
+            Self::Lunar => "Lunar Client",
+            Self::SKLauncher => "SKLauncher",
+            Self::TLauncher => "TLauncher",
         }
     }
 }
 
-#[derive(Clone)]
-pub struct ScanMod(pub &'static str, pub &'static str);
-
-#[derive(Eq, Clone, Debug)]
-pub struct DiscoveredMod<'a>(pub &'a str, pub &'a str);
-
-impl PartialEq for DiscoveredMod<'_> {
-    fn eq(&self, other: &Self) -> bool {
-        self.0 == other.0
-    }
-}
-
-impl Hash for DiscoveredMod<'_> {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.0.hash(state);
-    }
-}
-
-pub struct KnownMod<'a>(pub ScanMod, pub DiscoveredMod<'a>);
-
 pub struct EnvironmentContext<'a> {
     pub launcher: Option<Launcher>,
     pub mc_version: Option<String>,
+    /// What Mojang's version manifest says about `mc_version`, only filled in when the bot looks
+    /// it up.
+    pub game_version: Option<GameVersion>,
+    pub java_version: Option<String>,
+    pub java_vendor: Option<String>,
+    pub os: Option<String>,
+    pub arch: Option<String>,
+    /// Only present in crash reports.
+    pub system: Option<SystemDetails<'a>>,
     pub loader: Option<ModLoader>,
     pub discovered_mods: HashSet<DiscoveredMod<'a>>,
-    pub known_mods: Vec<KnownMod<'a>>,
     pub map_status: &'a MapStatus,
+    pub exceptions: Vec<JavaException<'a>>,
+    pub records: Vec<LogRecord<'a>>,
+    /// Installed mods with a newer release, only filled in when the bot looks them up.
+    pub updates: Vec<ModUpdate>,
+    /// Stable versions of the loader, newest first, only filled in when the bot looks them up.
+    pub loader_versions: Vec<String>,
 }
 
-impl Display for EnvironmentContext<'_> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+/// Parts of the summary from [`EnvironmentContext::describe`], for declarative checks that
+/// repeat them to name in their `covered_by`.
+pub const DESCRIBED_PROPERTIES: &[&str] = &[
+    "environment.launcher",
+    "environment.minecraft",
+    "environment.loader",
+    "environment.java",
+];
+
+impl EnvironmentContext<'_> {
+    /// Whether the summary shows one of the [`DESCRIBED_PROPERTIES`].
+    pub fn describes(&self, property: &str) -> bool {
+        match property {
+            "environment.launcher" => self.launcher.is_some(),
+            "environment.minecraft" => self.mc_version.is_some(),
+            "environment.loader" => self.loader.is_some(),
+            "environment.java" => self.java_version.is_some(),
+            _ => false,
+        }
+    }
+
+    /// A summary of the environment for the top of the embed.
+    pub fn describe(&self, display: &DisplayData) -> String {
+        let mut output = String::new();
         if let Some(launcher) = &self.launcher {
-            write!(f, "**Launcher:** {}\n", launcher)?;
+            let _ = write!(output, "**Launcher:** {}\n", display.launcher(launcher));
         }
         if let Some(version) = &self.mc_version {
-            write!(f, "**Minecraft:** `{}`\n", version)?;
+            let _ = write!(output, "**Minecraft:** `{}`\n", version);
         }
         if let Some(loader) = &self.loader {
-            write!(f, "**Loader:** {}\n", loader)?;
+            let _ = write!(output, "**Loader:** {}\n", display.loader(loader));
+        }
+        if let Some(version) = &self.java_version {
+            let _ = match &self.java_vendor {
+                Some(vendor) => write!(output, "**Java:** `{version}` ({vendor})\n"),
+                None => write!(output, "**Java:** `{version}`\n"),
+            };
+        }
+        match (&self.os, &self.arch) {
+            (Some(os), Some(arch)) => {
+                let _ = write!(output, "**System:** {os} ({arch})\n");
+            }
+            (Some(os), None) => {
+                let _ = write!(output, "**System:** {os}\n");
+            }
+            _ => {}
+        }
+        if let Some(system) = &self.system {
+            if let Some(cpu) = system.cpu {
+                let _ = write!(output, "**CPU:** {cpu}\n");
+            }
+            if let Some(gpu) = &system.gpu {
+                let _ = write!(output, "**GPU:** {}\n", gpu.renderer);
+            }
+            if let Some(memory) = system.max_memory {
+                let _ = write!(output, "**Memory:** `{memory} MiB`\n");
+            }
         }
         if let MapStatus::Mapped(took) = self.map_status {
-            write!(f, "**Remapped in:** `{}ms`\n", took.as_millis())?;
+            let _ = write!(output, "**Remapped in:** `{}ms`\n", took.as_millis());
+        }
+        let errors = self.records.iter().filter(|r| r.level >= Level::Error).count();
+        let warnings = self.records.iter().filter(|r| r.level == Level::Warn).count();
+        if errors > 0 || warnings > 0 {
+            let _ = write!(output, "**Records:** `{errors}` errors, `{warnings}` warnings\n");
         }
-        if !self.known_mods.is_empty() {
-            write!(f, "\n")?;
-            write!(f, "**Detected Mods:**\n")?;
-            for ele in &self.known_mods {
-                write!(f, "- {} `{}`\n", ele.0 .1, ele.1 .1)?;
+        let installed = self.discovered_mods.iter().filter(|m| !m.builtin).count();
+        if installed > 0 {
+            let bundled = self
+                .discovered_mods
+                .iter()
+                .filter(|m| !m.builtin && m.parent.is_some())
+                .count();
+            let _ = write!(output, "**Mods:** `{installed}` (`{bundled}` bundled)\n");
+        }
+        let highlighted = display.highlighted_mods(&self.discovered_mods);
+        if !highlighted.is_empty() {
+            output.push_str("\n**Detected Mods:**\n");
+            for (highlighted_mod, discovered) in &highlighted {
+                let _ = write!(
+                    output,
+                    "- {} `{}`\n",
+                    display.highlighted_mod(highlighted_mod),
+                    discovered.version
+                );
             }
-            write!(
-                f,
+            let _ = write!(
+                output,
                 "- and {} more...\n",
-                self.discovered_mods.len() - self.known_mods.len()
-            )?;
+                installed.saturating_sub(highlighted.len())
+            );
         }
-        Ok(())
+        output
     }
 }
 
@@ -117,7 +200,11 @@ macro_rules! truncate {
         let mut result = $string;
         let length = result.len();
         if length > $max_len {
-            result.truncate($max_len - 3);
+            let mut end = $max_len - 3;
+            while !result.is_char_boundary(end) {
+                end -= 1;
+            }
+            result.truncate(end);
             result = format!("{result}...");
         }
         result
@@ -128,7 +215,7 @@ macro_rules! truncate {
 macro_rules! grab_all {
     ($log:expr,$($arg:expr),*) => {'a: {
         $(
-            if let Some(cap) = Regex::new($arg).expect("Incorrect regex").captures($log) {
+            if let Some(cap) = $crate::static_regex!($arg).captures($log) {
                 break 'a Some(cap);
             }
         )*
@@ -136,11 +223,23 @@ macro_rules! grab_all {
     }};
 }
 
+#[macro_export]
+macro_rules! grab_every {
+    ($log:expr,$($arg:expr),*) => {{
+        let mut all = vec![];
+        $(
+            all.extend($crate::static_regex!($arg).captures_iter($log));
+        )*
+        all.sort_by_key(|cap| cap.get(0).map_or(0, |m| m.start()));
+        all
+    }};
+}
+
 #[macro_export]
 macro_rules! grab {
     ($log:expr,$max_len:expr,$($arg:expr),*) => {'a: {
         $(
-            if let Some(cap) = Regex::new($arg).expect("Incorrect regex").captures($log) {
+            if let Some(cap) = $crate::static_regex!($arg).captures($log) {
                 break 'a Some(cap.get(1).map(|m| truncate!(m.as_str().to_string(), $max_len)));
             }
         )*
@@ -152,7 +251,7 @@ macro_rules! grab {
 macro_rules! peek {
     ($log:expr,$($arg:expr),*) => {'a: {
         $(
-            if Regex::new($arg).expect("Incorrect regex").is_match($log) {
+            if $crate::static_regex!($arg).is_match($log) {
                 break 'a true;
             }
         )*
@@ -182,13 +281,71 @@ pub fn read_mc_version(log: &str) -> Option<String> {
         32,
         r"Loading Minecraft ([^\s]+)",
         r"minecraft server version ([^\s]+)",
-        r"Minecraft Version: ([^\s]+)"
+        r"Minecraft Version: ([^\s]+)",
+        r"--fml\.mcVersion, ([^\s,\]]+)"
     )
     .map(|o| o.expect("Regex error!!!"))
 }
 
-pub fn get_environment_info<'a>(log: &'a str, map_status: &'a MapStatus) -> EnvironmentContext<'a> {
-    let launcher = if peek!(log, r"Prism Launcher version:") {
+/// The version of Java the game ran on, as printed by launchers and crash reports.
+fn read_java_version(log: &str) -> Option<String> {
+    grab!(
+        log,
+        32,
+        r"Java is version ([^\s,]+)",
+        r"Java Version: ([^\s,]+)",
+        r"[Jj]ava version ([^\s,]+) by"
+    )
+    .flatten()
+}
+
+fn read_java_vendor(log: &str) -> Option<String> {
+    grab!(
+        log,
+        64,
+        r"(?m)Java is version .+ architecture, from (.+?)\.?\r?$",
+        r"(?m)Java Version: [^,\r\n]+, (.+?)\r?$",
+        r"[Jj]ava version \S+ by ([^;\r\n]+)"
+    )
+    .flatten()
+}
+
+/// The operating system, as printed by crash reports and Forge, falling back on the kind of
+/// paths in the log.
+fn read_os(log: &str) -> Option<String> {
+    if let Some(os) = grab!(
+        log,
+        64,
+        r"Operating System: (.+?) \(",
+        r"; OS (.+?) arch "
+    ) {
+        return os;
+    }
+    if peek!(log, r"[A-Z]:\\(?:Users|Program Files)") {
+        Some("Windows".to_string())
+    } else if peek!(log, r"/Users/[^/\s]+/Library/") {
+        Some("macOS".to_string())
+    } else if peek!(log, r"/home/[^/\s]+/") {
+        Some("Linux".to_string())
+    } else {
+        None
+    }
+}
+
+fn read_arch(log: &str) -> Option<String> {
+    grab!(
+        log,
+        16,
+        r"Operating System: .+? \((\w+)\) version",
+        r"; OS .+? arch (\w+)",
+        r"Java is version .+, using \d+ \((\w+)\) architecture"
+    )
+    .flatten()
+}
+
+fn read_launcher(log: &str) -> Option<Launcher> {
+    // Launchers that reuse the official launcher's folders go before it
+    if peek!(log, r"Prism Launcher version:") {
         Some(Launcher::Prism)
     } else if peek!(log, r"PolyMC version:") {
         Some(Launcher::PolyMC)
@@ -196,9 +353,48 @@ pub fn get_environment_info<'a>(log: &'a str, map_status: &'a MapStatus) -> Envi
         Some(Launcher::MultiMC)
     } else if peek!(log, r"[\\/]com\.modrinth\.theseus[\\/]") {
         Some(Launcher::Theseus)
+    } else if peek!(log, r"(?i)[\\/]atlauncher[\\/]") {
+        Some(Launcher::ATLauncher)
+    } else if peek!(log, r"(?i)[\\/]gdlauncher(?:_next|_carbon)?[\\/]") {
+        Some(Launcher::GDLauncher)
+    } else if peek!(log, r"(?i)[\\/]\.technic[\\/]") {
+        Some(Launcher::Technic)
+    } else if peek!(log, r"(?i)[\\/]\.lunarclient[\\/]", r"com\.moonsworth\.lunar") {
+        Some(Launcher::Lunar)
+    } else if peek!(log, r"(?i)[\\/]\.?sklauncher[\\/]") {
+        Some(Launcher::SKLauncher)
+    } else if peek!(
+        log,
+        // Only its own files and servers, as players mention it in chat
+        r"(?i)[\\/]\.tlauncher[\\/]",
+        r"(?i)\bauth\.tlauncher\.org\b",
+        r"\borg\.tlauncher\."
+    ) {
+        Some(Launcher::TLauncher)
+    } else if peek!(log, r"(?i)[\\/]curseforge[\\/]minecraft[\\/]") {
+        Some(Launcher::CurseForge)
+    } else if peek!(
+        log,
+        r"minecraft\.launcher\.brand=minecraft-launcher",
+        r"[\\/]\.minecraft[\\/]versions[\\/]"
+    ) {
+        Some(Launcher::Vanilla)
     } else {
         None
-    };
+    }
+}
+
+/// The major version of Java, like `17` for `17.0.8` and `8` for `1.8.0_381`.
+pub fn java_major_version(version: &str) -> Option<u32> {
+    let mut parts = version.split(['.', '_', '+', '-']);
+    match parts.next()? {
+        "1" => parts.next()?.parse().ok(),
+        major => major.parse().ok(),
+    }
+}
+
+pub fn get_environment_info<'a>(log: &'a str, map_status: &'a MapStatus) -> EnvironmentContext<'a> {
+    let launcher = read_launcher(log);
 
     let mut loader = None;
 
@@ -210,18 +406,26 @@ pub fn get_environment_info<'a>(log: &'a str, map_status: &'a MapStatus) -> Envi
         r"Is Modded: Definitely; [^\s]+ brand changed to 'fabric'"
     ) {
         loader = Some(ModLoader::Fabric(fabric_version));
-    } else if peek!(
-        log,
-        r"ne\.mi\.fm\.lo",
-        r"Is Modded: Definitely; [^\s]+ brand changed to 'forge'"
-    ) {
-        loader = Some(ModLoader::Forge);
-    } else if peek!(
+    } else if let Some(neoforge_version) = grab!(
         log,
+        32,
+        // NeoForge for 1.20.1 still uses Forge's packages, so it has to be ruled out first
+        r"--fml\.neoForgeVersion, ([^\s,\]]+)",
+        r"NeoForge: net\.neoforged:([^\s]+)",
         r"net\.neoforged\.fml\.loading",
         r"Is Modded: Definitely; [^\s]+ brand changed to 'neoforge'"
     ) {
-        loader = Some(ModLoader::NeoForge);
+        loader = Some(ModLoader::NeoForge(neoforge_version));
+    } else if let Some(forge_version) = grab!(
+        log,
+        32,
+        r"--fml\.forgeVersion, ([^\s,\]]+)",
+        r"Forge: net\.minecraftforge:([^\s]+)",
+        r"MinecraftForge v([^\s]+) Initialized",
+        r"ne\.mi\.fm\.lo",
+        r"Is Modded: Definitely; [^\s]+ brand changed to 'forge'"
+    ) {
+        loader = Some(ModLoader::Forge(forge_version));
     } else if let Some(quilt_version) = grab!(
         log,
         32,
@@ -233,143 +437,40 @@ pub fn get_environment_info<'a>(log: &'a str, map_status: &'a MapStatus) -> Envi
 
     let mc_version = read_mc_version(log);
 
-    let discovered_mods = [
-        Regex::new(r"\n\s*- (\S+) (\S+)"),
-        Regex::new(r"\n\s*(\S+): .+ (\S+)"),
-        Regex::new(r"mod '.+' \((\S+)\) (\S+)"),
-        Regex::new(
-            r"\| \s*\w+ \| [^|]* \| (\S+)\s* \| (\S+)\s* \| [^|]* \| \w+\s* \| [^|]* \| [^|]* \|",
-        ),
-    ]
-    .into_iter()
-    .flat_map(|r| {
-        r.expect("brombeere")
-            .captures_iter(log)
-            .map(|cap| {
-                let mod_id = cap.get(1).expect("Regex err").as_str();
-                let mod_version = cap.get(2).expect("Regex err 2").as_str();
-                DiscoveredMod(mod_id, mod_version)
-            })
-            .collect::<Vec<_>>()
-    })
-    .collect::<HashSet<_>>();
-
-    let scan_mods = vec![
-        ScanMod("fabric", "<:fabric:1246103308842700831> Fabric API"),
-        ScanMod("fabric-api", "<:fabric:1246103308842700831> Fabric API"),
-        ScanMod(
-            "quilt_base",
-            "<:quilt:1246170627652718653> Quilt Standard Libraries",
-        ),
-        ScanMod(
-            "quilted_fabric_api",
-            "<:quilt:1246170627652718653> Quilted Fabric API",
-        ),
-        ScanMod(
-            "do_a_barrel_roll",
-            "<:doabarrelroll:1107712867823792210> Do a Barrel Roll",
-        ),
-        ScanMod(
-            "showmeyourskin",
-            "<:showmeyourskin:1107713046987686009> Show Me Your Skin",
-        ),
-        ScanMod(
-            "rolling_down_in_the_deep",
-            "<:rollingdowninthedeep:1246194315580145734> Rolling Down in the Deep",
-        ),
-        ScanMod(
-            "mini_tardis",
-            "<:minitardis:1246194819739549707> Mini Tardis",
-        ),
-        ScanMod(
-            "skinshuffle",
-            "<:skinshuffle:1120649582502756392> SkinShuffle",
-        ),
-        ScanMod(
-            "omnihopper",
-            "<:omnihopper:1107713581446873158> Omni-Hopper",
-        ),
-        ScanMod(
-            "recursiveresources",
-            "<:recursiveresources:1107713344355442799> Recursive Resources",
-        ),
-        ScanMod(
-            "shared-resources",
-            "<:sharedresources:1107713221063872532> Shared Resources",
-        ),
-        ScanMod(
-            "clientpaintings",
-            "<:clientpaintings:1107713678712774778> Client Paintings",
-        ),
-        ScanMod(
-            "moderate-loading-screen",
-            "<:moderateloadingscreen:1107713920271122462> Mod-erate Loading Screen",
-        ),
-        ScanMod(
-            "blahaj-totem",
-            "<:shork:1172685466676502559> Blåhaj of Undying",
-        ),
-        ScanMod(
-            "restart_detector",
-            "<:restartdetector:1172685600000847922> Restart Detector",
-        ),
-        ScanMod("trickster", "<:trickster:1254515857640394876> Trickster"),
-        ScanMod(
-            "headpats",
-            "<:headpats:1282096343006838825> Headpat a Friend!",
-        ),
-        ScanMod("cicada", "<:cicada:1246197518807863367> CICADA"),
-        ScanMod(
-            "elytratrims",
-            "<:elytratrims:1246408624423702558> Elytra Trims",
-        ),
-        ScanMod(
-            "soundboard",
-            "<:soundboard:1246447385362698280> Voice Chat Soundboard",
-        ),
-        ScanMod("particular", "<:particular:1247196807688228876> Particular"),
-        ScanMod(
-            "inventory_sort",
-            "<:chaiinventorysorter:1247198105745494086> Chai's Inventory Sorter",
-        ),
-        ScanMod("wowozela", "<:wowozela:1254516583032819732> Wowozela"),
-        ScanMod(
-            "flightassistant",
-            "<:flightassistant:1248205945494966334> FlightAssistant",
-        ),
-        ScanMod("neruina", "<:neruina:1247198104017571900> Neruina"),
-        ScanMod(
-            "midnightlib",
-            "<:midnightlib:1247198101018513569> MidnightLib",
-        ),
-        ScanMod("zauber", "<:zauber:1247198102230798516> Zauber"),
-        ScanMod("owo", "<:owo:1246492160027656273> oωo"),
-        ScanMod(
-            "yet_another_config_lib_v3",
-            "<:yacl:1248028103775752212> Yet Another Config Lib",
-        ),
-        // Shitass mods lmao
-        ScanMod("optifabric", "<:optifabric:1246484303110606978> OptiFabric"),
-        ScanMod("bclib", "<:bclib:1246585932379852901> BCLib"),
-        ScanMod("feather", "<:feather:1247213091591028756> Feather Client"),
-    ];
-
-    let known_mods = discovered_mods
+    let discovered_mods = parse_mod_list(log);
+
+    // Fabric lists Java as a mod, which is its major version only
+    let java_version = discovered_mods
         .iter()
-        .filter_map(|d| {
-            scan_mods
-                .iter()
-                .find(|s| s.0 == d.0)
-                .map(|s| KnownMod(s.clone(), d.clone()))
-        })
-        .collect();
+        .find(|m| m.id == "java")
+        .map(|m| m.version.to_string())
+        .or_else(|| read_java_version(log));
+
+    // Forge crash reports don't always print the loader version outside of the mod list
+    if let Some(ModLoader::Forge(version) | ModLoader::NeoForge(version)) = &mut loader
+        && version.is_none()
+    {
+        *version = discovered_mods
+            .iter()
+            .find(|m| m.id == "forge" || m.id == "neoforge")
+            .map(|m| m.version.to_string());
+    }
 
     EnvironmentContext {
         launcher,
         mc_version,
+        game_version: None,
+        java_version,
+        java_vendor: read_java_vendor(log),
+        os: read_os(log),
+        arch: read_arch(log),
+        system: parse_system_details(log),
         loader,
         discovered_mods,
-        known_mods,
         map_status,
+        exceptions: parse_exceptions(log),
+        records: parse_records(log),
+        updates: vec![],
+        loader_versions: vec![],
     }
 }
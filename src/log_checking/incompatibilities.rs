@@ -0,0 +1,151 @@
+use std::fs;
+
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use serde::Deserialize;
+
+use super::{
+    checks::{CheckAction, CheckReport, Evidence, Severity},
+    environment::EnvironmentContext,
+    versions::VersionRange,
+};
+
+#[derive(Deserialize, Clone)]
+pub struct RequiredMod {
+    pub id: String,
+    /// Only match these versions of the mod, any version if left out.
+    pub versions: Option<VersionRange>,
+}
+
+/// A regex that's matched against the whole log.
+#[derive(Deserialize, Clone)]
+#[serde(try_from = "String")]
+pub struct LogPattern(Regex);
+
+impl TryFrom<String> for LogPattern {
+    type Error = regex::Error;
+
+    fn try_from(pattern: String) -> Result<Self, Self::Error> {
+        Regex::new(&pattern).map(LogPattern)
+    }
+}
+
+/// A combination of mods known to cause problems, loaded from `incompatibilities.toml`. A single
+/// mod works too, for mods that cause problems with pretty much anything.
+#[derive(Deserialize, Clone)]
+pub struct Incompatibility {
+    /// Identifier of the report, stays the same when the title changes.
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub severity: Severity,
+    /// Every one of these has to be installed.
+    pub mods: Vec<RequiredMod>,
+    /// Only match on these versions of the game, any version if left out.
+    pub minecraft: Option<VersionRange>,
+    /// Log text that shows the mods are installed, for logs where the mod list couldn't be read.
+    /// Mod version ranges aren't checked for these.
+    #[serde(default)]
+    pub patterns: Vec<LogPattern>,
+    #[serde(default)]
+    pub actions: Vec<CheckAction>,
+}
+
+#[derive(Deserialize)]
+struct IncompatibilityFile {
+    #[serde(default)]
+    incompatibilities: Vec<Incompatibility>,
+}
+
+pub fn load_incompatibilities() -> Result<Vec<Incompatibility>> {
+    parse_incompatibilities(&fs::read_to_string("./incompatibilities.toml")?)
+}
+
+fn parse_incompatibilities(text: &str) -> Result<Vec<Incompatibility>> {
+    let incompatibilities = toml::from_str::<IncompatibilityFile>(text)?.incompatibilities;
+    // Every log has all of no mods installed
+    if let Some(empty) = incompatibilities.iter().find(|i| i.mods.is_empty()) {
+        return Err(anyhow!("Incompatibility {} has no mods", empty.id));
+    }
+    Ok(incompatibilities)
+}
+
+impl Incompatibility {
+    pub fn check(&self, log: &str, ctx: &EnvironmentContext) -> Option<CheckReport> {
+        let mc_version = ctx.mc_version.as_deref();
+        if let Some(range) = &self.minecraft
+            && !mc_version.is_some_and(|v| range.matches(v, None))
+        {
+            return None;
+        }
+
+        let all_installed = self.mods.iter().all(|required| {
+            ctx.discovered_mods.iter().any(|m| {
+                m.id == required.id
+                    && required
+                        .versions
+                        .as_ref()
+                        .is_none_or(|range| range.matches(m.version, mc_version))
+            })
+        });
+        let evidence = if all_installed {
+            None
+        } else if ctx.discovered_mods.iter().all(|m| m.builtin) {
+            let captures = self.patterns.iter().find_map(|p| p.0.captures(log))?;
+            Some(Evidence::from_captures(log, &captures))
+        } else {
+            // The mod list was read and ruled this out
+            return None;
+        };
+
+        Some(CheckReport {
+            id: self.id.clone(),
+            title: self.title.clone(),
+            description: self.description.clone(),
+            severity: self.severity,
+            evidence,
+            actions: self.actions.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_incompatibilities;
+    use crate::{log_checking::environment::get_environment_info, log_upload::MapStatus};
+
+    const ENTRY: &str = r#"
+[[incompatibilities]]
+id = "optifabric"
+title = "OptiFabric detected"
+description = ""
+severity = "High"
+mods = [{ id = "optifabric" }]
+patterns = ['me\.modmuss50\.optifabric']
+"#;
+
+    #[test]
+    fn patterns_only_without_mod_list() {
+        let incompatibilities = parse_incompatibilities(ENTRY).unwrap();
+        let trace = "\tat me.modmuss50.optifabric.mod.OptifineSetup.run(OptifineSetup.java:41)\n";
+
+        let no_mod_list = trace.to_string();
+        let ctx = get_environment_info(&no_mod_list, &MapStatus::NotRequired);
+        let report = incompatibilities[0].check(&no_mod_list, &ctx).unwrap();
+        assert_eq!(report.evidence.unwrap().line, 1);
+
+        let with_mod_list = format!(
+            "[main/INFO]: Loading 3 mods:\n\t- fabricloader 0.15.11\n\t- minecraft 1.20.1\n\t- sodium 0.5.8\n{trace}"
+        );
+        let ctx = get_environment_info(&with_mod_list, &MapStatus::NotRequired);
+        assert!(incompatibilities[0].check(&with_mod_list, &ctx).is_none());
+    }
+
+    #[test]
+    fn rejects_entries_without_mods() {
+        let err = parse_incompatibilities(&ENTRY.replace(r#"[{ id = "optifabric" }]"#, "[]"))
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), "Incompatibility optifabric has no mods");
+    }
+}
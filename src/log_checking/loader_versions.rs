@@ -0,0 +1,63 @@
+use std::{collections::HashMap, sync::Mutex, time::Instant};
+
+use anyhow::Result;
+use serde::Deserialize;
+
+use crate::{
+    constants::{FABRIC_META_URL, LOADER_VERSIONS_TTL, QUILT_META_URL},
+    util::create_http,
+};
+
+use super::environment::ModLoader;
+
+#[derive(Deserialize)]
+struct LoaderVersion {
+    version: String,
+    /// Only given by Fabric, Quilt marks unstable versions with a pre-release suffix instead.
+    stable: Option<bool>,
+}
+
+/// Remembers the released versions of Fabric and Quilt loader for [`LOADER_VERSIONS_TTL`].
+pub struct LoaderVersionCache {
+    cache: Mutex<HashMap<&'static str, (Instant, Vec<String>)>>,
+}
+
+impl LoaderVersionCache {
+    pub fn create() -> LoaderVersionCache {
+        LoaderVersionCache {
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The stable versions of the loader, newest first. Empty for loaders that aren't looked up.
+    pub async fn stable_versions(&self, loader: &ModLoader) -> Result<Vec<String>> {
+        let (name, url) = match loader {
+            ModLoader::Fabric(_) => ("fabric", format!("{FABRIC_META_URL}/versions/loader")),
+            ModLoader::Quilt(_) => ("quilt", format!("{QUILT_META_URL}/versions/loader")),
+            ModLoader::Forge(_) | ModLoader::NeoForge(_) => return Ok(vec![]),
+        };
+
+        if let Some((fetched, versions)) = self.cache.lock().expect("Poisoned loader cache").get(name)
+            && fetched.elapsed() < LOADER_VERSIONS_TTL
+        {
+            return Ok(versions.clone());
+        }
+
+        let versions = create_http()?
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Vec<LoaderVersion>>()
+            .await?
+            .into_iter()
+            .filter(|v| v.stable.unwrap_or(!v.version.contains('-')))
+            .map(|v| v.version)
+            .collect::<Vec<_>>();
+        self.cache
+            .lock()
+            .expect("Poisoned loader cache")
+            .insert(name, (Instant::now(), versions.clone()));
+        Ok(versions)
+    }
+}
@@ -0,0 +1,223 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::Instant,
+};
+
+use anyhow::Result;
+use serde::Deserialize;
+
+use crate::{
+    constants::{END_OF_LIFE_YEARS, VERSION_MANIFEST_TTL, VERSION_MANIFEST_URL},
+    util::create_http,
+};
+
+/// The first version to need each major version of Java, newest first. Versions released before
+/// all of these run on Java 8.
+const JAVA_REQUIREMENTS: &[(&str, u32)] = &[("24w14a", 21), ("1.18-pre2", 17), ("21w19a", 16)];
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum VersionType {
+    Release,
+    Snapshot,
+    OldBeta,
+    OldAlpha,
+}
+
+#[derive(Deserialize)]
+struct ManifestEntry {
+    id: String,
+    #[serde(rename = "type")]
+    version_type: VersionType,
+    /// ISO 8601, so these can be compared as strings.
+    #[serde(rename = "releaseTime")]
+    release_time: String,
+}
+
+#[derive(Deserialize)]
+struct Latest {
+    release: String,
+    snapshot: String,
+}
+
+/// Mojang's list of every version of the game, newest first.
+#[derive(Deserialize)]
+pub struct VersionManifest {
+    latest: Latest,
+    versions: Vec<ManifestEntry>,
+}
+
+/// What the manifest knows about the version of the game in a log.
+pub struct GameVersion {
+    pub id: String,
+    pub version_type: VersionType,
+    pub release_time: String,
+    /// Major version of Java the game needs to run.
+    pub java: u32,
+    /// The newest version of the same type.
+    pub latest: String,
+    /// Whether the version is old enough that mods have mostly stopped supporting it.
+    pub end_of_life: bool,
+}
+
+impl VersionManifest {
+    /// The manifest that ships with the bot, for when Mojang can't be reached. It's downloaded by
+    /// `scripts/update-version-manifest.sh`.
+    pub fn bundled() -> Result<VersionManifest> {
+        VersionManifest::read(Path::new("./minecraft_versions.json"))
+    }
+
+    pub fn read(path: &Path) -> Result<VersionManifest> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    fn entry(&self, id: &str) -> Option<&ManifestEntry> {
+        self.versions.iter().find(|v| v.id == id)
+    }
+
+    pub fn game_version(&self, id: &str) -> Option<GameVersion> {
+        let entry = self.entry(id)?;
+        let java = JAVA_REQUIREMENTS
+            .iter()
+            .find(|(first, _)| {
+                self.entry(first)
+                    .is_some_and(|first| first.release_time <= entry.release_time)
+            })
+            .map_or(8, |(_, java)| *java);
+        let latest = match entry.version_type {
+            VersionType::Snapshot => &self.latest.snapshot,
+            _ => &self.latest.release,
+        };
+
+        // Anything released more than a few years before the latest release
+        let end_of_life = self.entry(&self.latest.release).is_some_and(|latest| {
+            let year = latest.release_time[..4].parse::<u32>().unwrap_or_default();
+            let cutoff = format!(
+                "{}{}",
+                year.saturating_sub(END_OF_LIFE_YEARS),
+                &latest.release_time[4..]
+            );
+            entry.release_time < cutoff
+        });
+
+        Some(GameVersion {
+            id: entry.id.clone(),
+            version_type: entry.version_type,
+            release_time: entry.release_time.clone(),
+            java,
+            latest: latest.clone(),
+            end_of_life,
+        })
+    }
+}
+
+/// Keeps Mojang's version manifest in memory, refreshing it every [`VERSION_MANIFEST_TTL`] and
+/// storing it on disk so a restart doesn't lose it when Mojang is down.
+pub struct VersionManifestCache {
+    /// When the manifest was last downloaded, along with the manifest itself.
+    manifest: Mutex<(Option<Instant>, Option<VersionManifest>)>,
+}
+
+/// Where the manifest is stored between restarts, systemd's cache directory if there is one.
+fn cache_path() -> PathBuf {
+    PathBuf::from(env::var("CACHE_DIRECTORY").unwrap_or("./cache".to_string()))
+        .join("version_manifest.json")
+}
+
+impl VersionManifestCache {
+    /// Starts out with the manifest from the last run, or the bundled one.
+    pub fn create() -> VersionManifestCache {
+        let manifest = fs::read_to_string(cache_path())
+            .map_err(anyhow::Error::from)
+            .and_then(|s| Ok(serde_json::from_str::<VersionManifest>(&s)?))
+            .or_else(|_| VersionManifest::bundled())
+            .map_err(|err| println!("Failed to load version manifest: {err}"))
+            .ok();
+        VersionManifestCache {
+            manifest: Mutex::new((None, manifest)),
+        }
+    }
+
+    /// Downloads the manifest again if it's out of date. On failure the old one is kept, and no
+    /// new attempt is made until it would be out of date again.
+    pub async fn refresh(&self) -> Result<()> {
+        {
+            let mut manifest = self.manifest.lock().expect("Poisoned version manifest");
+            if manifest.0.is_some_and(|fetched| fetched.elapsed() < VERSION_MANIFEST_TTL) {
+                return Ok(());
+            }
+            manifest.0 = Some(Instant::now());
+        }
+
+        let text = create_http()?
+            .get(VERSION_MANIFEST_URL)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        let downloaded = serde_json::from_str::<VersionManifest>(&text)?;
+        self.manifest.lock().expect("Poisoned version manifest").1 = Some(downloaded);
+
+        let path = cache_path();
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(path, text).await?;
+        Ok(())
+    }
+
+    pub fn game_version(&self, id: &str) -> Option<GameVersion> {
+        let manifest = self.manifest.lock().expect("Poisoned version manifest");
+        manifest.1.as_ref()?.game_version(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{VersionManifest, VersionType};
+
+    fn manifest() -> VersionManifest {
+        VersionManifest::read(Path::new("tests/fixtures/version_manifest.json"))
+            .expect("loading version manifest")
+    }
+
+    #[test]
+    fn java_requirements() {
+        let manifest = manifest();
+        let java = |id| manifest.game_version(id).map(|v| v.java);
+        assert_eq!(java("1.21.1"), Some(21));
+        assert_eq!(java("24w14a"), Some(21));
+        assert_eq!(java("1.20.4"), Some(17));
+        assert_eq!(java("1.18-pre2"), Some(17));
+        assert_eq!(java("1.17.1"), Some(16));
+        assert_eq!(java("1.16.5"), Some(8));
+        assert_eq!(java("1.99"), None);
+    }
+
+    #[test]
+    fn snapshots() {
+        let manifest = manifest();
+        let snapshot = manifest.game_version("24w14a").unwrap();
+        assert_eq!(snapshot.version_type, VersionType::Snapshot);
+        assert_eq!(snapshot.latest, "24w33a");
+
+        let release = manifest.game_version("1.20.1").unwrap();
+        assert_eq!(release.version_type, VersionType::Release);
+        assert_eq!(release.latest, "1.21.1");
+    }
+
+    #[test]
+    fn end_of_life() {
+        let manifest = manifest();
+        let end_of_life = |id| manifest.game_version(id).unwrap().end_of_life;
+        assert!(!end_of_life("1.20.1"));
+        assert!(!end_of_life("1.18"));
+        assert!(end_of_life("1.17.1"));
+        assert!(end_of_life("b1.7.3"));
+    }
+}
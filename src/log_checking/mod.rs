@@ -1,19 +1,82 @@
-use crate::log_upload;
+use crate::{
+    constants::{EMBED_FIELD_NAME_LIMIT, EMBED_FIELD_VALUE_LIMIT},
+    log_upload, truncate, DisplayDataKey, IncompatibilitiesKey, LoaderVersionCacheKey, LogChecksKey,
+    UpdateCacheKey, VersionManifestKey,
+};
 
 use self::{
-    checks::{check_checks, Severity},
-    environment::get_environment_info,
+    checks::{check_checks, write_list, CheckAction, CheckReport, Severity},
+    diff::LogDiff,
+    environment::{get_environment_info, EnvironmentContext},
 };
-use serenity::all::CreateEmbed;
+use serenity::{all::CreateEmbed, prelude::TypeMap};
+use std::fmt::Write;
 use tokio::time::Instant;
 
+pub mod blame;
 pub mod checks;
+pub mod declarative;
+pub mod diff;
+pub mod display;
 pub mod environment;
+pub mod incompatibilities;
+pub mod loader_versions;
+pub mod minecraft;
+pub mod mods;
+pub mod records;
+pub mod stacktrace;
+pub mod system;
+pub mod updates;
+pub mod versions;
+
+/// Reads the environment out of a log, looks up everything the checks need online, then runs them.
+async fn analyze_log<'a>(
+    log: &'a str,
+    map_status: &'a log_upload::MapStatus,
+    data: &TypeMap,
+) -> (EnvironmentContext<'a>, Vec<CheckReport>) {
+    let declarative_checks = data.get::<LogChecksKey>().expect("No log checks?");
+    let incompatibilities = data.get::<IncompatibilitiesKey>().expect("No incompatibilities?");
+    let update_cache = data.get::<UpdateCacheKey>().expect("No update cache?");
+    let loader_version_cache = data
+        .get::<LoaderVersionCacheKey>()
+        .expect("No loader version cache?");
+    let version_manifest = data
+        .get::<VersionManifestKey>()
+        .expect("No version manifest?");
+
+    let mut ctx = get_environment_info(log, map_status);
+    if let Some(mc_version) = &ctx.mc_version {
+        if let Err(err) = version_manifest.refresh().await {
+            println!("Failed to refresh version manifest: {err}");
+        }
+        ctx.game_version = version_manifest.game_version(mc_version);
+    }
+    match update_cache.find_updates(&ctx).await {
+        Ok(updates) => ctx.updates = updates,
+        Err(err) => println!("Failed to look up mod updates: {err}"),
+    }
+    if let Some(loader) = &ctx.loader {
+        match loader_version_cache.stable_versions(loader).await {
+            Ok(versions) => ctx.loader_versions = versions,
+            Err(err) => println!("Failed to look up loader versions: {err}"),
+        }
+    }
+    let checks = check_checks(log, &ctx, declarative_checks, incompatibilities);
+    (ctx, checks)
+}
+
+pub async fn check_logs(
+    log: &str,
+    name: &str,
+    t: &log_upload::LogType,
+    map_status: &log_upload::MapStatus,
+    data: &TypeMap,
+) -> (CreateEmbed, Vec<CheckAction>) {
+    let display = data.get::<DisplayDataKey>().expect("No display data?");
 
-pub fn check_logs(log: &str, name: &str, t: &log_upload::LogType, map_status: &log_upload::MapStatus) -> CreateEmbed {
     let start = Instant::now();
-    let ctx = get_environment_info(log, map_status);
-    let checks = check_checks(log, &ctx);
+    let (ctx, checks) = analyze_log(log, map_status, data).await;
     let severity = checks
         .iter()
         .map(|r| r.severity)
@@ -25,7 +88,8 @@ pub fn check_logs(log: &str, name: &str, t: &log_upload::LogType, map_status: &l
         .title(t.title_format(name, &took))
         .color(severity.get_color())
         .description(format!(
-            "{ctx}{}",
+            "{}{}",
+            ctx.describe(display),
             if checks.is_empty() {
                 ""
             } else if matches!(severity, Severity::None) {
@@ -35,12 +99,108 @@ pub fn check_logs(log: &str, name: &str, t: &log_upload::LogType, map_status: &l
             }
         ));
 
-    for ele in &checks {
+    let mut actions: Vec<CheckAction> = vec![];
+    for ele in checks {
+        let note = match &ele.evidence {
+            Some(evidence) if evidence.excerpt.trim().is_empty() => {
+                format!("\n-# Line {}", evidence.line)
+            }
+            // Backticks in the line would end the code span early
+            Some(evidence) => format!(
+                "\n-# Line {}: `{}`",
+                evidence.line,
+                evidence.excerpt.trim().replace('`', "'")
+            ),
+            None => String::new(),
+        };
+        // The description gives way, so the line it's about is always shown
+        let value = format!(
+            "{}{note}",
+            truncate!(ele.description, EMBED_FIELD_VALUE_LIMIT - note.len())
+        );
         embed = embed.field(
-            format!("{} {}", ele.severity.get_emoji(), &ele.title),
-            &ele.description,
+            truncate!(
+                format!("{} {}", display.severity(ele.severity), &ele.title),
+                EMBED_FIELD_NAME_LIMIT
+            ),
+            value,
             false,
         );
+        for action in ele.actions {
+            if action.has_valid_url() && !actions.contains(&action) {
+                actions.push(action);
+            }
+        }
+    }
+
+    (embed, actions)
+}
+
+/// Runs both logs through the checks and lists what changed from the old one to the new one.
+pub async fn diff_logs(
+    old: &str,
+    old_map_status: &log_upload::MapStatus,
+    new: &str,
+    new_map_status: &log_upload::MapStatus,
+    data: &TypeMap,
+) -> CreateEmbed {
+    let display = data.get::<DisplayDataKey>().expect("No display data?");
+
+    let (old_ctx, old_checks) = analyze_log(old, old_map_status, data).await;
+    let (new_ctx, new_checks) = analyze_log(new, new_map_status, data).await;
+    let diff = LogDiff::new((&old_ctx, &old_checks), (&new_ctx, &new_checks));
+
+    // Colored by whatever went wrong in the new log
+    let severity = diff
+        .new_checks
+        .iter()
+        .map(|(severity, _)| *severity)
+        .max()
+        .unwrap_or(Severity::None);
+    let mut embed = CreateEmbed::new().color(severity.get_color());
+    if diff.is_empty() {
+        return embed.description("No differences found between the logs.");
+    }
+
+    if !diff.environment.is_empty() {
+        let mut description = String::new();
+        for (name, old, new) in &diff.environment {
+            let _ = writeln!(description, "**{name}:** `{old}` → `{new}`");
+        }
+        embed = embed.description(description);
+    }
+
+    let mod_list = |mods: &[(&str, &str)]| {
+        mods.iter()
+            .map(|(id, version)| format!("`{id}` `{version}`"))
+            .collect::<Vec<_>>()
+    };
+    let check_titles = |checks: &[(Severity, String)]| {
+        checks
+            .iter()
+            .map(|(severity, title)| format!("{} {title}", display.severity(*severity)))
+            .collect::<Vec<_>>()
+    };
+    let lists = [
+        ("Added mods", mod_list(&diff.added_mods)),
+        ("Removed mods", mod_list(&diff.removed_mods)),
+        (
+            "Updated mods",
+            diff.updated_mods
+                .iter()
+                .map(|(id, old, new)| format!("`{id}` `{old}` → `{new}`"))
+                .collect(),
+        ),
+        ("New issues", check_titles(&diff.new_checks)),
+        ("Resolved issues", check_titles(&diff.resolved_checks)),
+    ];
+    for (name, items) in lists {
+        if items.is_empty() {
+            continue;
+        }
+        let mut value = String::new();
+        write_list(&mut value, items.into_iter());
+        embed = embed.field(name, truncate!(value, EMBED_FIELD_VALUE_LIMIT), false);
     }
 
     embed
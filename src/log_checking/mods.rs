@@ -0,0 +1,222 @@
+use std::{collections::HashSet, hash::Hash};
+
+use crate::static_regex;
+
+/// Mods provided by the loader or the game itself, rather than installed by the user.
+const BUILTIN_MODS: &[&str] = &[
+    "java",
+    "minecraft",
+    "fabricloader",
+    "quilt_loader",
+    "mixinextras",
+    "forge",
+    "neoforge",
+];
+
+/// Name parts of Forge mod files that separate the mod's name from its version.
+const FILE_NAME_LOADERS: &[&str] = &["forge", "neoforge", "fabric", "quilt"];
+
+#[derive(Eq, Clone, Debug)]
+pub struct DiscoveredMod<'a> {
+    pub id: &'a str,
+    pub version: &'a str,
+    /// The mod this one was bundled in through jar-in-jar, if any.
+    pub parent: Option<&'a str>,
+    pub builtin: bool,
+}
+
+impl PartialEq for DiscoveredMod<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Hash for DiscoveredMod<'_> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl<'a> DiscoveredMod<'a> {
+    fn new(id: &'a str, version: &'a str, parent: Option<&'a str>) -> DiscoveredMod<'a> {
+        DiscoveredMod {
+            id,
+            version,
+            parent,
+            builtin: BUILTIN_MODS.contains(&id),
+        }
+    }
+}
+
+/// Keeps track of the most recent mod at every indentation level, to find the parent of nested
+/// entries.
+struct Nesting<'a>(Vec<(usize, &'a str)>);
+
+impl<'a> Nesting<'a> {
+    fn push(&mut self, column: usize, id: &'a str) -> Option<&'a str> {
+        while self.0.last().is_some_and(|(c, _)| *c >= column) {
+            self.0.pop();
+        }
+        let parent = self.0.last().map(|(_, id)| *id);
+        self.0.push((column, id));
+        parent
+    }
+}
+
+/// Parses Fabric and Quilt's `Loading N mods:` block, where bundled mods are nested like
+/// `   |-- fabric-api-base 0.4.31`.
+fn parse_loading_block<'a>(log: &'a str, mods: &mut Vec<DiscoveredMod<'a>>) {
+    let Some(start) = static_regex!(r"Loading \d+ mods:\r?\n").find(log) else {
+        return;
+    };
+
+    let mut nesting = Nesting(vec![]);
+    for line in log[start.end()..].lines() {
+        let Some(captures) =
+            static_regex!(r"^(\s*(?:- |(?:\|   |    )*[|\\]-- ))(\S+) (\S+)").captures(line)
+        else {
+            break;
+        };
+        let column = captures.get(1).expect("Regex err").as_str().trim_end().len();
+        let id = captures.get(2).expect("Regex err").as_str();
+        let version = captures.get(3).expect("Regex err").as_str();
+        let parent = nesting.push(column, id);
+        mods.push(DiscoveredMod::new(id, version, parent));
+    }
+}
+
+/// Parses Quilt's mod table, where bundled mods have a file path like `<mods>/a.jar!/b.jar`.
+fn parse_quilt_table<'a>(log: &'a str, mods: &mut Vec<DiscoveredMod<'a>>) {
+    let rows = static_regex!(r"(?m)^\|\s*\d+ \|.*\|\s*$")
+        .find_iter(log)
+        .filter_map(|row| {
+            let columns = row.as_str().split('|').map(str::trim).collect::<Vec<_>>();
+            // Leading empty column, index, name, id, version, plugin, flags, files, hash
+            match columns.as_slice() {
+                [_, _, _, id, version, _, _, files, ..] if !id.is_empty() => {
+                    Some((*id, *version, *files))
+                }
+                _ => None,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    for (id, version, files) in &rows {
+        let parent = files.rsplit_once("!/").and_then(|(outer, _)| {
+            rows.iter()
+                .find(|(_, _, f)| *f == outer)
+                .map(|(parent, _, _)| *parent)
+        });
+        mods.push(DiscoveredMod::new(id, version, parent));
+    }
+}
+
+/// Parses the `Fabric Mods:` or `Quilt Mods:` section of a crash report, where bundled mods are
+/// indented one level deeper than their parent.
+fn parse_crash_report_section<'a>(log: &'a str, mods: &mut Vec<DiscoveredMod<'a>>) {
+    let Some(header) = static_regex!(r"(?m)^(\s*)(?:Fabric|Quilt) Mods: *\r?\n").captures(log)
+    else {
+        return;
+    };
+    let header_indent = header.get(1).expect("Regex err").as_str().len();
+    let start = header.get(0).expect("Regex err").end();
+
+    let mut nesting = Nesting(vec![]);
+    for line in log[start..].lines() {
+        let indent = line.len() - line.trim_start().len();
+        if indent <= header_indent {
+            break;
+        }
+        let Some(captures) = static_regex!(r"^\s*(\S+): .*? (\S+)\s*$").captures(line) else {
+            continue;
+        };
+        let id = captures.get(1).expect("Regex err").as_str();
+        let version = captures.get(2).expect("Regex err").as_str();
+        let parent = nesting.push(indent, id);
+        mods.push(DiscoveredMod::new(id, version, parent));
+    }
+}
+
+/// Parses the `Mod List:` table in Forge and NeoForge crash reports.
+fn parse_forge_mod_list<'a>(log: &'a str, mods: &mut Vec<DiscoveredMod<'a>>) {
+    let Some(header) = static_regex!(r"(?m)^(\s*)Mod List: *\r?\n").captures(log) else {
+        return;
+    };
+    let header_indent = header.get(1).expect("Regex err").as_str().len();
+    let start = header.get(0).expect("Regex err").end();
+
+    for line in log[start..].lines() {
+        if line.len() - line.trim_start().len() <= header_indent {
+            break;
+        }
+        // File, name, id, version, state, manifest
+        if let [_, _, id, version, ..] = line.split('|').map(str::trim).collect::<Vec<_>>()[..]
+            && !id.is_empty()
+        {
+            mods.push(DiscoveredMod::new(id, version, None));
+        }
+    }
+}
+
+/// Guesses the id and version of a mod from its file name, as Forge only logs the files it found
+/// outside of crash reports. `sodium-neoforge-0.6.0-beta.1+mc1.20.6.jar` becomes `sodium` version
+/// `0.6.0-beta.1+mc1.20.6`.
+fn guess_from_file_name(file_name: &str) -> Option<(&str, &str)> {
+    let stem = file_name.strip_suffix(".jar")?;
+    let mut boundaries = stem.match_indices(['-', '_']).map(|(i, _)| i);
+
+    // The name ends before the first part that's a version or a loader
+    let name_end = boundaries.find(|&i| {
+        let part = stem[i + 1..].split(['-', '_']).next().unwrap_or_default();
+        part.starts_with(|c: char| c.is_ascii_digit())
+            || FILE_NAME_LOADERS.contains(&part.to_lowercase().as_str())
+    })?;
+    let name = &stem[..name_end];
+
+    let mut version = &stem[name_end + 1..];
+    loop {
+        let (part, rest) = version.split_once(['-', '_']).unwrap_or((version, ""));
+        // Skip loader names and a leading Minecraft version, as long as something's left
+        if rest.is_empty()
+            || !(FILE_NAME_LOADERS.contains(&part.to_lowercase().as_str())
+                || static_regex!(r"^(?:mc)?1\.\d+(?:\.\d+)?$").is_match(part))
+        {
+            break;
+        }
+        version = rest;
+    }
+
+    (!name.is_empty()).then_some((name, version))
+}
+
+fn parse_found_mod_files<'a>(log: &'a str, mods: &mut Vec<DiscoveredMod<'a>>) {
+    for captures in
+        static_regex!(r#"Found mod file "?([^"\s]+\.jar)"? of type MOD"#).captures_iter(log)
+    {
+        let file_name = captures.get(1).expect("Regex err").as_str();
+        if let Some((id, version)) = guess_from_file_name(file_name) {
+            mods.push(DiscoveredMod::new(id, version, None));
+        }
+    }
+}
+
+/// Finds every mod listed in the log, preferring the loader's own mod list and falling back on
+/// mods mentioned in dependency errors, which are printed before the list is, and finally the
+/// file names Forge logs.
+pub fn parse_mod_list(log: &str) -> HashSet<DiscoveredMod<'_>> {
+    let mut mods = vec![];
+    parse_loading_block(log, &mut mods);
+    parse_quilt_table(log, &mut mods);
+    parse_crash_report_section(log, &mut mods);
+    parse_forge_mod_list(log, &mut mods);
+
+    for captures in static_regex!(r"[Mm]od '.+?' \((\S+)\) (\S+)").captures_iter(log) {
+        let id = captures.get(1).expect("Regex err").as_str();
+        let version = captures.get(2).expect("Regex err").as_str();
+        mods.push(DiscoveredMod::new(id, version, None));
+    }
+    parse_found_mod_files(log, &mut mods);
+
+    // Sets keep the first value inserted, so the most complete listing of a mod wins
+    mods.into_iter().collect()
+}
@@ -0,0 +1,113 @@
+use std::fmt::Display;
+
+use crate::static_regex;
+
+#[derive(PartialEq, PartialOrd, Eq, Ord, Clone, Copy, Debug)]
+pub enum Level {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+    Fatal,
+}
+
+impl Level {
+    fn parse(level: &str) -> Option<Level> {
+        match level {
+            "TRACE" => Some(Level::Trace),
+            "DEBUG" => Some(Level::Debug),
+            "INFO" => Some(Level::Info),
+            "WARN" | "WARNING" => Some(Level::Warn),
+            "ERROR" | "SEVERE" => Some(Level::Error),
+            "FATAL" => Some(Level::Fatal),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Level::Trace => "TRACE",
+            Level::Debug => "DEBUG",
+            Level::Info => "INFO",
+            Level::Warn => "WARN",
+            Level::Error => "ERROR",
+            Level::Fatal => "FATAL",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// A single log4j record, like `[12:34:56] [Render thread/ERROR] (Minecraft) Something broke`.
+pub struct LogRecord<'a> {
+    pub time: &'a str,
+    pub thread: &'a str,
+    pub level: Level,
+    /// The logger name, as given by Fabric's `(Logger)` or Forge's `[logger/MARKER]` formats.
+    pub logger: Option<&'a str>,
+    /// The message, along with any continuation lines like stack traces.
+    pub message: &'a str,
+    /// 1-based line number of the record in the log.
+    pub line: usize,
+}
+
+impl<'a> LogRecord<'a> {
+    /// The first line of the message.
+    pub fn summary(&self) -> &'a str {
+        self.message.lines().next().unwrap_or_default()
+    }
+}
+
+/// Splits a log into records, covering the vanilla, Fabric and Forge layouts, along with lines
+/// that a launcher prefixed with its own timestamp. Lines that don't start a record are treated
+/// as a continuation of the previous one, and anything before the first record is skipped.
+pub fn parse_records(log: &str) -> Vec<LogRecord<'_>> {
+    let regex = static_regex!(
+        r"^[^\[]{0,40}\[([^\]]+)\] \[(.+?)/(TRACE|DEBUG|INFO|WARN|WARNING|ERROR|SEVERE|FATAL)\](?: \[([^\]]+)\])?(?: \(([^)]+)\))?:? ?"
+    );
+
+    let mut records: Vec<LogRecord> = vec![];
+    let mut offset = 0;
+    for (i, line) in log.split('\n').enumerate() {
+        let line_offset = offset;
+        offset += line.len() + 1;
+        let line = line.trim_end_matches('\r');
+
+        let captures = if line.contains('[') {
+            regex.captures(line)
+        } else {
+            None
+        };
+        match captures {
+            Some(captures) => {
+                let Some(level) = captures.get(3).and_then(|m| Level::parse(m.as_str())) else {
+                    continue;
+                };
+                let start = captures.get(0).expect("Regex err").end();
+                records.push(LogRecord {
+                    time: captures.get(1).expect("Regex err").as_str(),
+                    thread: captures.get(2).expect("Regex err").as_str(),
+                    level,
+                    // Forge appends a marker to the logger, like `[net.minecraftforge.fml.loading/SCAN]`
+                    logger: captures
+                        .get(4)
+                        .or(captures.get(5))
+                        .and_then(|m| m.as_str().split('/').next()),
+                    message: &line[start..],
+                    line: i + 1,
+                });
+            }
+            None => {
+                if let Some(record) = records.last_mut()
+                    && !line.trim().is_empty()
+                {
+                    let start = record.message.as_ptr() as usize - log.as_ptr() as usize;
+                    record.message = &log[start..line_offset + line.len()];
+                }
+            }
+        }
+    }
+    records
+}
@@ -0,0 +1,368 @@
+use std::fmt::Display;
+
+use crate::static_regex;
+
+/// Continuation lines of an exception message before we assume it wasn't one.
+const MAX_MESSAGE_LINES: usize = 32;
+
+pub struct StackFrame<'a> {
+    /// Fully qualified method, possibly prefixed by a module like `java.base/`.
+    pub method: &'a str,
+    /// Whatever is between the parentheses, usually `File.java:123`.
+    pub location: &'a str,
+    /// Anything after the location, like the `~[some-mod.jar:?]` jar info Fabric and Forge add.
+    pub extra: Option<&'a str>,
+    /// 1-based line number in the log.
+    pub line: usize,
+}
+
+impl StackFrame<'_> {
+    /// The method's class, without any module prefix.
+    pub fn class_name(&self) -> &str {
+        let method = self.method.rsplit('/').next().unwrap_or(self.method);
+        method.rsplit_once('.').map_or(method, |(class, _)| class)
+    }
+}
+
+pub struct JavaException<'a> {
+    /// Fully qualified exception class, like `java.lang.NullPointerException`.
+    pub class: &'a str,
+    pub message: Option<&'a str>,
+    pub frames: Vec<StackFrame<'a>>,
+    /// Frames left out through `... N more`, as they're shared with the enclosing trace.
+    pub omitted_frames: usize,
+    pub caused_by: Option<Box<JavaException<'a>>>,
+    pub suppressed: Vec<JavaException<'a>>,
+    /// 1-based line number of the exception header in the log.
+    pub line: usize,
+}
+
+impl<'a> JavaException<'a> {
+    /// The innermost `Caused by:` of this exception, or itself if it has no cause.
+    pub fn root_cause(&self) -> &JavaException<'a> {
+        let mut current = self;
+        while let Some(cause) = &current.caused_by {
+            current = cause;
+        }
+        current
+    }
+
+    /// This exception followed by all of its causes, outermost first.
+    pub fn chain(&self) -> impl Iterator<Item = &JavaException<'a>> {
+        std::iter::successors(Some(self), |e| e.caused_by.as_deref())
+    }
+
+    /// This exception along with all of its causes and suppressed exceptions, and theirs.
+    pub fn all(&self) -> Vec<&JavaException<'a>> {
+        let mut all = vec![];
+        let mut stack = vec![self];
+        while let Some(exception) = stack.pop() {
+            all.push(exception);
+            stack.extend(exception.suppressed.iter().rev());
+            if let Some(cause) = &exception.caused_by {
+                stack.push(cause);
+            }
+        }
+        all
+    }
+
+    pub fn simple_name(&self) -> &'a str {
+        self.class.rsplit('.').next().unwrap_or(self.class)
+    }
+
+    pub fn is(&self, class: &str) -> bool {
+        self.class == class
+    }
+
+    /// The first line of the message, if there is one.
+    pub fn summary(&self) -> Option<&'a str> {
+        self.message.and_then(|m| m.lines().next())
+    }
+}
+
+impl Display for JavaException<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.summary() {
+            Some(summary) => write!(f, "{}: {}", self.class, summary),
+            None => write!(f, "{}", self.class),
+        }
+    }
+}
+
+enum Kind {
+    Root,
+    CausedBy,
+    Suppressed,
+}
+
+/// An exception under construction, children are referenced by index until the tree is built.
+struct Node<'a> {
+    exception: Option<JavaException<'a>>,
+    indent: usize,
+    parent: Option<usize>,
+    caused_by: Option<usize>,
+    suppressed: Vec<usize>,
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+fn parse_header(line: &str) -> Option<(Kind, &str, Option<&str>)> {
+    // Cheap check first, this runs on every line of the log
+    if !(line.contains("Exception") || line.contains("Error") || line.contains("Throwable")) {
+        return None;
+    }
+    let captures = static_regex!(
+        r#"^\s*(?:Exception in thread "[^"]*" )?(?:(Caused by|Suppressed): )?((?:[a-zA-Z_$][\w$]*\.)+(?:[A-Z][\w$]*)?(?:Exception|Error|Throwable|Failure)[\w$]*)(?::\s?(.*))?$"#
+    )
+    .captures(line)?;
+    let kind = match captures.get(1).map(|m| m.as_str()) {
+        Some("Caused by") => Kind::CausedBy,
+        Some(_) => Kind::Suppressed,
+        None => Kind::Root,
+    };
+    Some((
+        kind,
+        captures.get(2)?.as_str(),
+        captures.get(3).map(|m| m.as_str().trim_end()),
+    ))
+}
+
+fn parse_frame(line: &str, number: usize) -> Option<StackFrame<'_>> {
+    if !line.trim_start().starts_with("at ") {
+        return None;
+    }
+    let captures = static_regex!(r"^\s+at (\S+?)\((.*?)\)(?:\s+(.*?))?\s*$").captures(line)?;
+    Some(StackFrame {
+        method: captures.get(1)?.as_str(),
+        location: captures.get(2)?.as_str(),
+        extra: captures.get(3).map(|m| m.as_str()),
+        line: number,
+    })
+}
+
+fn parse_omitted(line: &str) -> Option<usize> {
+    static_regex!(r"^\s+\.\.\. (\d+) more\s*$")
+        .captures(line)?
+        .get(1)?
+        .as_str()
+        .parse()
+        .ok()
+}
+
+/// Parses every Java exception in the log, along with its causes and suppressed exceptions.
+pub fn parse_exceptions(log: &str) -> Vec<JavaException<'_>> {
+    let mut nodes: Vec<Node> = vec![];
+    let mut roots = vec![];
+    // The exception that frames are currently being added to
+    let mut current: Option<usize> = None;
+    let mut message_lines = 0;
+
+    let mut offset = 0;
+    for (i, line) in log.split('\n').enumerate() {
+        let line_offset = offset;
+        offset += line.len() + 1;
+        let line = line.trim_end_matches('\r');
+        let number = i + 1;
+
+        if let Some(index) = current {
+            if let Some(frame) = parse_frame(line, number) {
+                if let Some(exception) = &mut nodes[index].exception {
+                    exception.frames.push(frame);
+                }
+                continue;
+            }
+            if let Some(omitted) = parse_omitted(line) {
+                if let Some(exception) = &mut nodes[index].exception {
+                    exception.omitted_frames = omitted;
+                }
+                continue;
+            }
+        }
+
+        if let Some((kind, class, message)) = parse_header(line) {
+            let indent = indent_of(line);
+            let parent = match (kind, current) {
+                (Kind::Root, _) | (_, None) => None,
+                // A cause belongs to the last exception printed at the same indentation
+                (Kind::CausedBy, Some(index)) => {
+                    let mut target = Some(index);
+                    while let Some(t) = target
+                        && nodes[t].indent > indent
+                    {
+                        target = nodes[t].parent;
+                    }
+                    target
+                }
+                // Suppressed exceptions are indented one level deeper than their owner
+                (Kind::Suppressed, Some(index)) => {
+                    let mut target = Some(index);
+                    while let Some(t) = target
+                        && nodes[t].indent >= indent
+                    {
+                        target = nodes[t].parent;
+                    }
+                    target
+                }
+            };
+
+            let index = nodes.len();
+            nodes.push(Node {
+                exception: Some(JavaException {
+                    class,
+                    message: message.filter(|m| !m.is_empty()),
+                    frames: vec![],
+                    omitted_frames: 0,
+                    caused_by: None,
+                    suppressed: vec![],
+                    line: number,
+                }),
+                indent,
+                parent,
+                caused_by: None,
+                suppressed: vec![],
+            });
+            match (parent, line.trim_start().starts_with("Suppressed: ")) {
+                (Some(p), true) => nodes[p].suppressed.push(index),
+                (Some(p), false) => {
+                    // Follow the existing chain, a cause of a cause is printed at the same level
+                    let mut tail = p;
+                    while let Some(next) = nodes[tail].caused_by {
+                        tail = next;
+                    }
+                    nodes[tail].caused_by = Some(index);
+                    nodes[index].parent = Some(tail);
+                }
+                (None, _) => roots.push(index),
+            }
+            current = Some(index);
+            message_lines = 0;
+            continue;
+        }
+
+        // Messages can span multiple lines, as long as no frames have been printed yet
+        if let Some(index) = current
+            && let Some(exception) = &mut nodes[index].exception
+            && exception.frames.is_empty()
+            && message_lines < MAX_MESSAGE_LINES
+            && !line.trim().is_empty()
+            && !line.starts_with('[')
+        {
+            let start = exception
+                .message
+                .map_or(line_offset, |m| m.as_ptr() as usize - log.as_ptr() as usize);
+            exception.message = Some(&log[start..line_offset + line.len()]);
+            message_lines += 1;
+            continue;
+        }
+
+        current = None;
+    }
+
+    roots.into_iter().map(|root| build(&mut nodes, root)).collect()
+}
+
+fn build<'a>(nodes: &mut [Node<'a>], index: usize) -> JavaException<'a> {
+    let mut exception = nodes[index].exception.take().expect("Exception built twice");
+    let suppressed = std::mem::take(&mut nodes[index].suppressed);
+    exception.suppressed = suppressed.into_iter().map(|i| build(nodes, i)).collect();
+    if let Some(cause) = nodes[index].caused_by {
+        exception.caused_by = Some(Box::new(build(nodes, cause)));
+    }
+    exception
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_exceptions;
+
+    #[test]
+    fn caused_by_chain() {
+        let log = "[12:00:00] [main/ERROR]: Crashed\n\
+java.lang.RuntimeException: outer\n\
+\tat a.b.C.run(C.java:1)\n\
+\tat a.b.D.main(D.java:2) ~[d.jar:?]\n\
+Caused by: java.lang.IllegalStateException: middle\n\
+\tat a.b.E.go(E.java:3)\n\
+\t... 2 more\n\
+Caused by: java.lang.NullPointerException\n\
+\tat a.b.F.x(F.java:4)\n\
+\t... 3 more\n";
+        let exceptions = parse_exceptions(log);
+        assert_eq!(exceptions.len(), 1);
+
+        let outer = &exceptions[0];
+        assert!(outer.is("java.lang.RuntimeException"));
+        assert_eq!(outer.message, Some("outer"));
+        assert_eq!(outer.line, 2);
+        assert_eq!(outer.frames.len(), 2);
+        assert_eq!(outer.frames[1].class_name(), "a.b.D");
+        assert_eq!(outer.frames[1].extra, Some("~[d.jar:?]"));
+        assert_eq!(outer.omitted_frames, 0);
+
+        let chain = outer.chain().collect::<Vec<_>>();
+        assert_eq!(chain.len(), 3);
+        assert_eq!(chain[1].message, Some("middle"));
+        assert_eq!(chain[1].omitted_frames, 2);
+        assert_eq!(chain[2].omitted_frames, 3);
+        assert_eq!(chain[2].line, 8);
+
+        let root = outer.root_cause();
+        assert!(root.is("java.lang.NullPointerException"));
+        assert_eq!(root.message, None);
+    }
+
+    #[test]
+    fn suppressed_with_own_cause() {
+        let log = "java.lang.Exception: main\n\
+\tat a.B.c(B.java:1)\n\
+\tSuppressed: java.io.IOException: close failed\n\
+\t\tat a.B.close(B.java:9)\n\
+\t\t... 1 more\n\
+\tCaused by: java.lang.IllegalStateException: inner\n\
+\t\tat a.B.d(B.java:5)\n\
+\t\t... 2 more\n\
+Caused by: java.lang.NullPointerException: npe\n\
+\tat a.B.e(B.java:7)\n\
+\t... 1 more\n";
+        let exceptions = parse_exceptions(log);
+        assert_eq!(exceptions.len(), 1);
+
+        let main = &exceptions[0];
+        assert_eq!(main.frames.len(), 1);
+        assert_eq!(main.suppressed.len(), 1);
+
+        let suppressed = &main.suppressed[0];
+        assert!(suppressed.is("java.io.IOException"));
+        assert_eq!(suppressed.omitted_frames, 1);
+        let suppressed_cause = suppressed.caused_by.as_deref().expect("No suppressed cause");
+        assert!(suppressed_cause.is("java.lang.IllegalStateException"));
+        assert_eq!(suppressed_cause.omitted_frames, 2);
+
+        assert!(main.root_cause().is("java.lang.NullPointerException"));
+        assert_eq!(main.root_cause().message, Some("npe"));
+
+        let all = main.all();
+        assert_eq!(all.len(), 4);
+        assert!(all.iter().any(|e| e.is("java.lang.IllegalStateException")));
+    }
+
+    #[test]
+    fn multiline_message_and_separate_traces() {
+        let log = "Exception in thread \"main\" java.lang.IllegalArgumentException: first line\n\
+second line\n\
+\tat a.B.c(B.java:1)\n\
+[12:00:01] [main/INFO]: Something else\n\
+net.example.CustomError\n\
+\tat a.B.d(B.java:2)\n";
+        let exceptions = parse_exceptions(log);
+        assert_eq!(exceptions.len(), 2);
+        assert_eq!(exceptions[0].message, Some("first line\nsecond line"));
+        assert_eq!(exceptions[0].summary(), Some("first line"));
+        assert_eq!(exceptions[0].frames.len(), 1);
+        assert!(exceptions[1].is("net.example.CustomError"));
+        assert_eq!(exceptions[1].simple_name(), "CustomError");
+        assert_eq!(exceptions[1].line, 5);
+    }
+}
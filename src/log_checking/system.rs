@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+
+use crate::{
+    constants::{AMD_MIN_DRIVER, INTEL_MIN_DRIVER},
+    static_regex,
+};
+
+/// Renderers that draw on the CPU, used when the graphics drivers are missing or broken.
+const SOFTWARE_RENDERERS: &[&str] = &[
+    "GDI Generic",
+    "Microsoft Basic Render Driver",
+    "llvmpipe",
+    "softpipe",
+];
+
+/// The graphics card the game ran on, from the crash report's `Backend API` line, which looks
+/// like `NVIDIA GeForce RTX 3070/PCIe/SSE2 GL version 4.6.0 NVIDIA 551.86, NVIDIA Corporation`.
+pub struct Gpu<'a> {
+    pub renderer: &'a str,
+    /// The OpenGL version along with the driver's own version.
+    pub gl_version: &'a str,
+    pub vendor: &'a str,
+}
+
+impl Gpu<'_> {
+    pub fn is_software(&self) -> bool {
+        SOFTWARE_RENDERERS.iter().any(|r| self.renderer.contains(r))
+    }
+
+    /// Whether the driver is old enough to be known for crashing the game. Only Intel and AMD
+    /// put a recognisable driver version in the OpenGL version.
+    pub fn outdated_driver(&self) -> bool {
+        // `4.6.0 - Build 31.0.101.4502`
+        if self.vendor.contains("Intel")
+            && let Some(captures) = static_regex!(r"Build (\d+)\.").captures(self.gl_version)
+        {
+            return captures[1].parse::<u32>().is_ok_and(|major| major < INTEL_MIN_DRIVER);
+        }
+        // `4.6.0 Compatibility Profile Context 23.12.1.231205`
+        if (self.vendor.contains("ATI") || self.vendor.contains("AMD"))
+            && let Some(captures) = static_regex!(r"Context (\d+)\.").captures(self.gl_version)
+        {
+            return captures[1].parse::<u32>().is_ok_and(|major| major < AMD_MIN_DRIVER);
+        }
+        false
+    }
+}
+
+/// The `-- System Details --` section at the end of crash reports.
+pub struct SystemDetails<'a> {
+    /// The most memory the game was allowed to use, in MiB.
+    pub max_memory: Option<u64>,
+    pub cpu: Option<&'a str>,
+    pub gpu: Option<Gpu<'a>>,
+    /// Every enabled resource pack, including the ones provided by the game and the loader.
+    pub resource_packs: Vec<&'a str>,
+}
+
+impl SystemDetails<'_> {
+    /// Resource packs added by the user, which are listed as `file/<name>`.
+    pub fn user_resource_packs(&self) -> impl Iterator<Item = &str> {
+        self.resource_packs
+            .iter()
+            .filter_map(|p| p.strip_prefix("file/"))
+            .map(|p| p.trim_end_matches(" (incompatible)"))
+    }
+}
+
+pub fn parse_system_details(log: &str) -> Option<SystemDetails<'_>> {
+    let start = log.find("-- System Details --")?;
+
+    // Only the top level entries, nested lines belong to lists like the mod list
+    let mut entries = HashMap::new();
+    for line in log[start..].lines().skip(1) {
+        if !line.is_empty() && !line.starts_with([' ', '\t']) && line != "Details:" {
+            break;
+        }
+        if let Some(captures) = static_regex!(r"^(?:\t| {4})(\S[^:]*): (.*)$").captures(line) {
+            let (_, [key, value]) = captures.extract();
+            entries.insert(key, value.trim());
+        }
+    }
+
+    let max_memory = entries
+        .get("Memory")
+        .and_then(|memory| static_regex!(r"up to \d+ bytes \((\d+) MiB\)").captures(memory))
+        .and_then(|captures| captures[1].parse().ok());
+    let cpu = entries
+        .get("CPU")
+        .filter(|cpu| !cpu.starts_with('<'))
+        // `12x AMD Ryzen 5 5600X 6-Core Processor`
+        .map(|cpu| {
+            static_regex!(r"^\d+x ")
+                .find(cpu)
+                .map_or(*cpu, |count| &cpu[count.end()..])
+        })
+        .or_else(|| entries.get("Processor Name").copied());
+    let gpu = entries.get("Backend API").and_then(|api| {
+        let (renderer, rest) = api.split_once(" GL version ")?;
+        let (gl_version, vendor) = rest.rsplit_once(", ")?;
+        Some(Gpu {
+            renderer,
+            gl_version,
+            vendor,
+        })
+    });
+    let resource_packs = entries
+        .get("Resource Packs")
+        .map(|packs| packs.split(", ").filter(|p| !p.is_empty()).collect())
+        .unwrap_or_default();
+
+    Some(SystemDetails {
+        max_memory,
+        cpu,
+        gpu,
+        resource_packs,
+    })
+}
@@ -0,0 +1,279 @@
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use anyhow::Result;
+use serde::Deserialize;
+use tokio::{sync::Semaphore, task::JoinSet};
+
+use crate::{
+    constants::{
+        MODRINTH_API_URL, MODRINTH_BATCH_SIZE, MODRINTH_CACHE_TTL, MODRINTH_CONCURRENT_LOOKUPS,
+        MODRINTH_LOOKUP_LIMIT, MODRINTH_PROJECT_URL,
+    },
+    util::create_http,
+};
+
+use super::{
+    environment::{EnvironmentContext, ModLoader},
+    versions::version_numbers,
+};
+
+/// An installed mod that has a newer release on Modrinth for the same game version and loader.
+pub struct ModUpdate {
+    pub mod_id: String,
+    pub installed: String,
+    pub latest: String,
+    pub url: String,
+}
+
+#[derive(Deserialize)]
+struct Project {
+    id: String,
+    slug: String,
+    loaders: Vec<String>,
+    game_versions: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct ProjectVersion {
+    id: String,
+    version_number: String,
+    version_type: String,
+}
+
+#[derive(Clone)]
+struct LatestVersion {
+    version_number: String,
+    url: String,
+}
+
+struct CacheEntry {
+    fetched: Instant,
+    /// `None` if the mod isn't on Modrinth, or has no release for this game version and loader.
+    latest: Option<LatestVersion>,
+}
+
+/// (mod id, Minecraft version, loader)
+type CacheKey = (String, String, &'static str);
+
+/// Remembers the latest Modrinth release of every mod looked up, so scanning the same modpack
+/// over and over only hits the API once every [`MODRINTH_CACHE_TTL`].
+pub struct UpdateCache {
+    cache: Mutex<HashMap<CacheKey, CacheEntry>>,
+}
+
+impl UpdateCache {
+    pub fn create() -> UpdateCache {
+        UpdateCache {
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Finds the installed mods that have a newer release, ignoring bundled and built-in mods,
+    /// as those are updated along with whatever provides them.
+    pub async fn find_updates(&self, ctx: &EnvironmentContext<'_>) -> Result<Vec<ModUpdate>> {
+        let (Some(mc_version), Some(loader)) = (&ctx.mc_version, &ctx.loader) else {
+            return Ok(vec![]);
+        };
+        let loader = modrinth_loader(loader);
+
+        let installed = ctx
+            .discovered_mods
+            .iter()
+            .filter(|m| !m.builtin && m.parent.is_none())
+            .collect::<Vec<_>>();
+
+        let missing = {
+            let mut cache = self.cache.lock().expect("Poisoned update cache");
+            cache.retain(|_, entry| entry.fetched.elapsed() < MODRINTH_CACHE_TTL);
+            installed
+                .iter()
+                .map(|m| m.id)
+                .filter(|id| !cache.contains_key(&(id.to_string(), mc_version.clone(), loader)))
+                .take(MODRINTH_LOOKUP_LIMIT)
+                .collect::<Vec<_>>()
+        };
+        if !missing.is_empty() {
+            let fetched = fetch_latest_versions(&missing, mc_version, loader).await?;
+            let mut cache = self.cache.lock().expect("Poisoned update cache");
+            for id in missing {
+                cache.insert(
+                    (id.to_string(), mc_version.clone(), loader),
+                    CacheEntry {
+                        fetched: Instant::now(),
+                        latest: fetched.get(id).cloned(),
+                    },
+                );
+            }
+        }
+
+        let cache = self.cache.lock().expect("Poisoned update cache");
+        let mut updates = installed
+            .iter()
+            .filter_map(|m| {
+                let entry = cache.get(&(m.id.to_string(), mc_version.clone(), loader))?;
+                let latest = entry.latest.as_ref()?;
+                (compare_versions(m.version, &latest.version_number, mc_version)
+                    == Some(Ordering::Less))
+                .then(|| ModUpdate {
+                    mod_id: m.id.to_string(),
+                    installed: m.version.to_string(),
+                    latest: latest.version_number.clone(),
+                    url: latest.url.clone(),
+                })
+            })
+            .collect::<Vec<_>>();
+        updates.sort_by(|a, b| a.mod_id.cmp(&b.mod_id));
+        Ok(updates)
+    }
+}
+
+fn modrinth_loader(loader: &ModLoader) -> &'static str {
+    match loader {
+        ModLoader::Fabric(_) => "fabric",
+        ModLoader::Forge(_) => "forge",
+        ModLoader::NeoForge(_) => "neoforge",
+        ModLoader::Quilt(_) => "quilt",
+    }
+}
+
+/// Resolves mod ids to Modrinth projects in batches, then looks up the latest version of every
+/// project that supports the game version and loader, a few at a time. Mods are assumed to use
+/// their id as slug. Projects that fail to look up are left out, so they're cached as having no
+/// release rather than being looked up again on the next scan.
+async fn fetch_latest_versions(
+    mod_ids: &[&str],
+    mc_version: &str,
+    loader: &'static str,
+) -> Result<HashMap<String, LatestVersion>> {
+    let client = create_http()?;
+    // Quilt loads Fabric mods too, and plenty of mods don't list Quilt separately
+    let loaders = match loader {
+        "quilt" => vec!["quilt", "fabric"],
+        loader => vec![loader],
+    };
+
+    let mut projects = vec![];
+    for batch in mod_ids.chunks(MODRINTH_BATCH_SIZE) {
+        let batch_projects = client
+            .get(format!("{MODRINTH_API_URL}/projects"))
+            .query(&[("ids", serde_json::to_string(batch)?)])
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Vec<Project>>()
+            .await?;
+        projects.extend(batch_projects.into_iter().filter(|p| {
+            p.game_versions.iter().any(|v| v == mc_version)
+                && p.loaders.iter().any(|l| loaders.contains(&l.as_str()))
+        }));
+    }
+
+    let permits = Arc::new(Semaphore::new(MODRINTH_CONCURRENT_LOOKUPS));
+    let mut lookups = JoinSet::new();
+    for project in projects {
+        // Projects are returned by id, so the mod id has to be matched up with the slug again
+        let Some(mod_id) = mod_ids
+            .iter()
+            .find(|id| id.eq_ignore_ascii_case(&project.slug))
+        else {
+            continue;
+        };
+        let mod_id = mod_id.to_string();
+        let client = client.clone();
+        let permits = permits.clone();
+        let query = [
+            ("loaders", serde_json::to_string(&loaders)?),
+            ("game_versions", serde_json::to_string(&[mc_version])?),
+        ];
+        lookups.spawn(async move {
+            let _permit = permits.acquire_owned().await?;
+            let versions = client
+                .get(format!("{MODRINTH_API_URL}/project/{}/version", project.id))
+                .query(&query)
+                .send()
+                .await?
+                .error_for_status()?
+                .json::<Vec<ProjectVersion>>()
+                .await?;
+            // Versions are sorted newest first, prefer releases over betas and alphas
+            let latest = versions
+                .iter()
+                .find(|v| v.version_type == "release")
+                .or(versions.first())
+                .map(|v| LatestVersion {
+                    version_number: v.version_number.clone(),
+                    url: format!("{MODRINTH_PROJECT_URL}/{}/version/{}", project.slug, v.id),
+                });
+            anyhow::Ok((mod_id, latest))
+        });
+    }
+
+    let mut latest_versions = HashMap::new();
+    while let Some(lookup) = lookups.join_next().await {
+        match lookup {
+            Ok(Ok((mod_id, Some(latest)))) => {
+                latest_versions.insert(mod_id, latest);
+            }
+            Ok(Ok((_, None))) => {}
+            Ok(Err(err)) => println!("Failed to look up mod versions: {err}"),
+            Err(err) => println!("Mod version lookup panicked: {err}"),
+        }
+    }
+    Ok(latest_versions)
+}
+
+/// Compares an installed version with one from Modrinth. Version numbers on Modrinth don't have
+/// to match the mod's metadata, so anything that can't be compared with confidence is `None`.
+fn compare_versions(installed: &str, latest: &str, mc_version: &str) -> Option<Ordering> {
+    if installed == latest {
+        return Some(Ordering::Equal);
+    }
+    let mc_version = Some(mc_version);
+    Some(version_numbers(installed, mc_version)?.cmp(&version_numbers(latest, mc_version)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use super::compare_versions;
+
+    #[test]
+    fn same_version() {
+        assert_eq!(
+            compare_versions("0.5.8+mc1.20.1", "0.5.8+mc1.20.1", "1.20.1"),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(
+            compare_versions("1.0", "1.0.0+1.20.1", "1.20.1"),
+            Some(Ordering::Equal)
+        );
+    }
+
+    #[test]
+    fn prefix_is_not_equal() {
+        assert_eq!(
+            compare_versions("1.0", "1.0.5+1.20.1", "1.20.1"),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            compare_versions("1.2", "11.2.0", "1.20.1"),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            compare_versions("3.0.1", "3.0", "1.20.1"),
+            Some(Ordering::Greater)
+        );
+    }
+
+    #[test]
+    fn unparseable() {
+        assert_eq!(compare_versions("beta", "1.0.0", "1.20.1"), None);
+        assert_eq!(compare_versions("1.0.0", "latest", "1.20.1"), None);
+    }
+}
@@ -0,0 +1,73 @@
+use std::cmp::Ordering;
+
+use serde::Deserialize;
+
+use crate::static_regex;
+
+/// The numeric part of a version, with the Minecraft version left out if given, so
+/// `0.5.8+mc1.20.1` and `mc1.20.1-0.5.8` both become `[0, 5, 8]`. Trailing zeroes are dropped,
+/// making `1.0` and `1.0.0` equal.
+pub fn version_numbers(version: &str, mc_version: Option<&str>) -> Option<Vec<u64>> {
+    let version = match mc_version {
+        Some(mc_version) => version.replace(mc_version, ""),
+        None => version.to_string(),
+    };
+    let numbers = static_regex!(r"\d+(?:\.\d+)*").find(&version)?;
+    let mut numbers = numbers
+        .as_str()
+        .split('.')
+        .map(str::parse)
+        .collect::<Result<Vec<u64>, _>>()
+        .ok()?;
+    while numbers.len() > 1 && numbers.last() == Some(&0) {
+        numbers.pop();
+    }
+    Some(numbers)
+}
+
+/// A set of version requirements like `>=0.6.0 <0.7`, separated by spaces or commas. A version
+/// without an operator has to match exactly.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(try_from = "String")]
+pub struct VersionRange(Vec<(Ordering, bool, Vec<u64>)>);
+
+impl TryFrom<String> for VersionRange {
+    type Error = String;
+
+    fn try_from(range: String) -> Result<Self, Self::Error> {
+        range
+            .split([' ', ','])
+            .filter(|part| !part.is_empty())
+            .map(|part| {
+                // (operator, the ordering it requires, whether equal versions match too)
+                let (operator, ordering, or_equal) = [
+                    (">=", Ordering::Greater, true),
+                    ("<=", Ordering::Less, true),
+                    (">", Ordering::Greater, false),
+                    ("<", Ordering::Less, false),
+                    ("=", Ordering::Equal, true),
+                ]
+                .into_iter()
+                .find(|(operator, _, _)| part.starts_with(operator))
+                .unwrap_or(("", Ordering::Equal, true));
+                let numbers = version_numbers(&part[operator.len()..], None)
+                    .ok_or_else(|| format!("invalid version `{part}` in range `{range}`"))?;
+                Ok((ordering, or_equal, numbers))
+            })
+            .collect::<Result<_, _>>()
+            .map(VersionRange)
+    }
+}
+
+impl VersionRange {
+    /// Whether a version satisfies every requirement. Versions without a number never do.
+    pub fn matches(&self, version: &str, mc_version: Option<&str>) -> bool {
+        let Some(numbers) = version_numbers(version, mc_version) else {
+            return false;
+        };
+        self.0.iter().all(|(ordering, or_equal, bound)| {
+            let actual = numbers.cmp(bound);
+            actual == *ordering || (*or_equal && actual == Ordering::Equal)
+        })
+    }
+}
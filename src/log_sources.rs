@@ -0,0 +1,409 @@
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use serde::{de::DeserializeOwned, Deserialize};
+use serenity::async_trait;
+
+use crate::{
+    config::Config,
+    constants::{
+        GITHUB_API_URL, GNOMEBIN_URL, HASTEBIN_URL, MAX_LOG_SIZE, MCLOGS_API_BASE_URL,
+        PASTEBIN_URL, PASTES_DEV_API_URL, PASTE_GG_API_BASE_URL,
+    },
+    static_regex,
+    util::{create_http, format_bytes},
+};
+
+/// A site logs can be linked from.
+#[async_trait]
+pub(crate) trait LogSource: Send + Sync {
+    /// Matches links to a log, the first group being what's passed to [`LogSource::fetch`].
+    fn pattern(&self) -> &Regex;
+
+    /// Downloads the log, `None` if the link turned out to have no log.
+    async fn fetch(&self, id: &str) -> Result<Option<String>>;
+
+    /// Name of the log shown on its button.
+    fn name<'a>(&self, id: &'a str) -> &'a str {
+        id
+    }
+
+    /// Whether the log has to be uploaded to mclo.gs before linking to it, because the site isn't
+    /// nice to read logs on.
+    fn needs_reupload(&self) -> bool {
+        true
+    }
+}
+
+/// Every supported site, sites that need configuring are left out when they aren't.
+pub(crate) fn log_sources(config: &Config) -> Vec<Box<dyn LogSource>> {
+    let mut sources: Vec<Box<dyn LogSource>> = vec![
+        Box::new(McLogs),
+        Box::new(PasteGg),
+        Box::new(Pastebin),
+        Box::new(Gist),
+        Box::new(PastesDev),
+        Box::new(Gnomebin),
+        Box::new(DiscordAttachment),
+        Box::new(RawGitHub),
+    ];
+    if let Some(token) = &config.hastebin_token {
+        sources.push(Box::new(Hastebin {
+            token: token.clone(),
+        }));
+    }
+    sources
+}
+
+/// The last part of a url's path, for sources that link to files.
+fn file_name(url: &str) -> &str {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// Downloads a response, refusing anything over [`MAX_LOG_SIZE`]. The body is counted as it
+/// comes in, as the length the server sends can be missing or wrong.
+async fn download(request: reqwest::RequestBuilder) -> Result<Vec<u8>> {
+    let mut response = request.send().await?.error_for_status()?;
+    if let Some(size) = response.content_length()
+        && size > MAX_LOG_SIZE.into()
+    {
+        return Err(anyhow!(
+            "Log size of {} exceeds the maximum allowed size of {}",
+            format_bytes(size.try_into().unwrap_or(u32::MAX)),
+            format_bytes(MAX_LOG_SIZE)
+        ));
+    }
+
+    let mut body = vec![];
+    while let Some(chunk) = response.chunk().await? {
+        if body.len() + chunk.len() > MAX_LOG_SIZE as usize {
+            return Err(anyhow!(
+                "Log exceeds the maximum allowed size of {}",
+                format_bytes(MAX_LOG_SIZE)
+            ));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
+
+/// Downloads a log as text, refusing anything over [`MAX_LOG_SIZE`].
+async fn download_text(request: reqwest::RequestBuilder) -> Result<String> {
+    Ok(String::from_utf8_lossy(&download(request).await?).into_owned())
+}
+
+/// Downloads a response from a site's API, refusing anything over [`MAX_LOG_SIZE`] as it
+/// contains the log.
+async fn download_json<T: DeserializeOwned>(request: reqwest::RequestBuilder) -> Result<T> {
+    Ok(serde_json::from_slice(&download(request).await?)?)
+}
+
+struct McLogs;
+
+#[async_trait]
+impl LogSource for McLogs {
+    fn pattern(&self) -> &Regex {
+        static_regex!(r"https:\/\/mclo\.gs\/([a-zA-Z0-9]+)")
+    }
+
+    async fn fetch(&self, id: &str) -> Result<Option<String>> {
+        let request = create_http()?.get(format!("{MCLOGS_API_BASE_URL}/1/raw/{id}"));
+        Ok(Some(download_text(request).await?))
+    }
+
+    fn needs_reupload(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Deserialize)]
+struct GGResponse {
+    result: GGResult,
+}
+
+#[derive(Deserialize)]
+struct GGResult {
+    files: Vec<GGFile>,
+}
+
+#[derive(Deserialize)]
+struct GGFile {
+    content: GGContent,
+}
+
+#[derive(Deserialize)]
+struct GGContent {
+    value: String,
+}
+
+struct PasteGg;
+
+#[async_trait]
+impl LogSource for PasteGg {
+    fn pattern(&self) -> &Regex {
+        static_regex!(r"https:\/\/paste\.gg\/p\/\w+\/([a-zA-Z0-9]+)")
+    }
+
+    async fn fetch(&self, id: &str) -> Result<Option<String>> {
+        let request = create_http()?.get(format!("{PASTE_GG_API_BASE_URL}/pastes/{id}?full=true"));
+        let mut response = download_json::<GGResponse>(request).await?;
+
+        if response.result.files.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(response.result.files.remove(0).content.value))
+    }
+}
+
+struct Pastebin;
+
+#[async_trait]
+impl LogSource for Pastebin {
+    fn pattern(&self) -> &Regex {
+        static_regex!(r"https:\/\/pastebin\.com\/(?:raw\/)?([a-zA-Z0-9]+)")
+    }
+
+    async fn fetch(&self, id: &str) -> Result<Option<String>> {
+        let request = create_http()?.get(format!("{PASTEBIN_URL}/raw/{id}"));
+        Ok(Some(download_text(request).await?))
+    }
+}
+
+#[derive(Deserialize)]
+struct GistResponse {
+    /// By file name, sorted to pick the same file every time.
+    files: BTreeMap<String, GistFile>,
+}
+
+#[derive(Deserialize)]
+struct GistFile {
+    /// Cut off for big files, which have to be downloaded from `raw_url` instead.
+    content: Option<String>,
+    truncated: Option<bool>,
+    raw_url: String,
+}
+
+struct Gist;
+
+#[async_trait]
+impl LogSource for Gist {
+    fn pattern(&self) -> &Regex {
+        static_regex!(r"https:\/\/gist\.github\.com\/(?:[\w-]+\/)?([0-9a-f]+)")
+    }
+
+    async fn fetch(&self, id: &str) -> Result<Option<String>> {
+        let client = create_http()?;
+        let response =
+            download_json::<GistResponse>(client.get(format!("{GITHUB_API_URL}/gists/{id}")))
+                .await?;
+
+        // Gists often come with a config file or a screenshot next to the log
+        let Some(file) = response
+            .files
+            .iter()
+            .find(|(name, _)| name.ends_with(".log") || name.ends_with(".txt"))
+            .or_else(|| response.files.iter().next())
+            .map(|(_, file)| file)
+        else {
+            return Ok(None);
+        };
+        match &file.content {
+            Some(content) if file.truncated != Some(true) => Ok(Some(content.clone())),
+            _ => Ok(Some(download_text(client.get(&file.raw_url)).await?)),
+        }
+    }
+}
+
+/// Toptal's hastebin, which needs an API token to read pastes.
+struct Hastebin {
+    token: String,
+}
+
+#[async_trait]
+impl LogSource for Hastebin {
+    fn pattern(&self) -> &Regex {
+        static_regex!(
+            r"https:\/\/(?:hastebin\.com|www\.toptal\.com\/developers\/hastebin)\/(?:share\/|raw\/)?([a-z]+)"
+        )
+    }
+
+    async fn fetch(&self, id: &str) -> Result<Option<String>> {
+        let request = create_http()?
+            .get(format!("{HASTEBIN_URL}/raw/{id}"))
+            .bearer_auth(&self.token);
+        Ok(Some(download_text(request).await?))
+    }
+}
+
+struct PastesDev;
+
+#[async_trait]
+impl LogSource for PastesDev {
+    fn pattern(&self) -> &Regex {
+        static_regex!(r"https:\/\/pastes\.dev\/([a-zA-Z0-9]+)")
+    }
+
+    async fn fetch(&self, id: &str) -> Result<Option<String>> {
+        let request = create_http()?.get(format!("{PASTES_DEV_API_URL}/{id}"));
+        Ok(Some(download_text(request).await?))
+    }
+}
+
+struct Gnomebin;
+
+#[async_trait]
+impl LogSource for Gnomebin {
+    fn pattern(&self) -> &Regex {
+        static_regex!(r"https:\/\/gnomebin\.com\/(?:raw\/)?([a-zA-Z0-9]+)")
+    }
+
+    async fn fetch(&self, id: &str) -> Result<Option<String>> {
+        let request = create_http()?.get(format!("{GNOMEBIN_URL}/raw/{id}"));
+        Ok(Some(download_text(request).await?))
+    }
+}
+
+/// Links to files uploaded to Discord, which people copy from other servers.
+struct DiscordAttachment;
+
+#[async_trait]
+impl LogSource for DiscordAttachment {
+    fn pattern(&self) -> &Regex {
+        // The whole link is needed, as it's signed
+        static_regex!(
+            r"(https:\/\/(?:cdn|media)\.discordapp\.(?:com|net)\/attachments\/\d+\/\d+\/[^\s?]+\.(?:log|txt)(?:\?[^\s>]*)?)"
+        )
+    }
+
+    async fn fetch(&self, url: &str) -> Result<Option<String>> {
+        Ok(Some(download_text(create_http()?.get(url)).await?))
+    }
+
+    fn name<'a>(&self, url: &'a str) -> &'a str {
+        file_name(url)
+    }
+}
+
+struct RawGitHub;
+
+#[async_trait]
+impl LogSource for RawGitHub {
+    fn pattern(&self) -> &Regex {
+        static_regex!(r"(https:\/\/(?:raw|gist)\.githubusercontent\.com\/[^\s>]+)")
+    }
+
+    async fn fetch(&self, url: &str) -> Result<Option<String>> {
+        Ok(Some(download_text(create_http()?.get(url)).await?))
+    }
+
+    fn name<'a>(&self, url: &'a str) -> &'a str {
+        file_name(url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sources() -> Vec<Box<dyn LogSource>> {
+        vec![
+            Box::new(McLogs),
+            Box::new(PasteGg),
+            Box::new(Pastebin),
+            Box::new(Gist),
+            Box::new(PastesDev),
+            Box::new(Gnomebin),
+            Box::new(DiscordAttachment),
+            Box::new(RawGitHub),
+            Box::new(Hastebin {
+                token: String::new(),
+            }),
+        ]
+    }
+
+    /// What every source that matches the message would fetch, by the source's index.
+    fn matches(message: &str) -> Vec<(usize, String)> {
+        sources()
+            .iter()
+            .enumerate()
+            .flat_map(|(i, source)| {
+                source
+                    .pattern()
+                    .captures_iter(message)
+                    .map(move |caps| (i, caps[1].to_string()))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn paste_sites() {
+        assert_eq!(matches("https://mclo.gs/aB3dE9f"), [(0, "aB3dE9f".into())]);
+        assert_eq!(
+            matches("https://paste.gg/p/anonymous/0a1b2c3d"),
+            [(1, "0a1b2c3d".into())]
+        );
+        assert_eq!(
+            matches("https://pastebin.com/Xy12AbCd"),
+            [(2, "Xy12AbCd".into())]
+        );
+        assert_eq!(
+            matches("https://pastebin.com/raw/Xy12AbCd"),
+            [(2, "Xy12AbCd".into())]
+        );
+        assert_eq!(
+            matches("https://pastes.dev/k2Lm9QpA"),
+            [(4, "k2Lm9QpA".into())]
+        );
+        assert_eq!(
+            matches("https://gnomebin.com/raw/qwerty"),
+            [(5, "qwerty".into())]
+        );
+        assert_eq!(
+            matches("https://www.toptal.com/developers/hastebin/share/abcdefgh"),
+            [(8, "abcdefgh".into())]
+        );
+    }
+
+    #[test]
+    fn gists() {
+        assert_eq!(
+            matches("https://gist.github.com/someone/1f2e3d4c5b6a"),
+            [(3, "1f2e3d4c5b6a".into())]
+        );
+        assert_eq!(
+            matches("https://gist.github.com/1f2e3d4c5b6a"),
+            [(3, "1f2e3d4c5b6a".into())]
+        );
+        // Raw files from a gist are read directly, not through the gist's API
+        let raw = "https://gist.githubusercontent.com/someone/1f2e3d4c5b6a/raw/9a8b7c/latest.log";
+        assert_eq!(matches(raw), [(7, raw.into())]);
+    }
+
+    #[test]
+    fn discord_attachments() {
+        let url = "https://cdn.discordapp.com/attachments/1234567890/9876543210/latest.log\
+?ex=66a1b2c3&is=66a06143&hm=0123456789abcdef0123456789abcdef&";
+        assert_eq!(matches(url), [(6, url.into())]);
+        assert_eq!(sources()[6].name(url), "latest.log");
+        // Angle brackets hide the embed, but aren't part of the link
+        assert_eq!(matches(&format!("<{url}>")), [(6, url.into())]);
+
+        let image = "https://media.discordapp.net/attachments/1234567890/9876543210/crash.png";
+        assert!(matches(image).is_empty());
+    }
+
+    #[test]
+    fn file_names() {
+        assert_eq!(
+            file_name("https://raw.githubusercontent.com/a/b/main/logs/latest.log#L10"),
+            "latest.log"
+        );
+        assert_eq!(
+            file_name("https://example.com/crash.txt?raw=1"),
+            "crash.txt"
+        );
+    }
+}
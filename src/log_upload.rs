@@ -2,49 +2,51 @@ use std::{
     borrow::Cow,
     io::{Cursor, ErrorKind, Read},
     path::Path,
+    sync::Arc,
     time::{Duration, Instant},
 };
 
 use anyhow::{anyhow, Result};
 use flate2::read::GzDecoder;
-use regex::Regex;
-use serde::{Deserialize, Serialize};
 use serenity::{
     all::{Attachment, Message},
-    builder::{CreateActionRow, CreateButton, CreateEmbed},
+    builder::{CreateActionRow, CreateButton, CreateEmbed, CreateEmbedFooter},
 };
 
-use serenity::client::Context;
+use serenity::{client::Context, prelude::TypeMap};
 
 use crate::{
-    constants::{MAX_LOG_SIZE, MCLOGS_API_BASE_URL, PASTEBIN_URL, PASTE_GG_API_BASE_URL},
-    log_checking::{check_logs, checks::Severity, environment::read_mc_version},
+    archive::{extract_logs, ArchiveKind},
+    constants::{BUTTON_LABEL_LIMIT, MAX_LOG_SIZE},
+    log_cache::{LogCache, ProcessedLog},
+    log_checking::{
+        check_logs,
+        checks::{CheckAction, Severity},
+        diff_logs,
+        environment::read_mc_version,
+    },
+    log_sources::{log_sources, LogSource},
     mappings::cache::MappingsCache,
-    util::{create_http, format_bytes},
-    ConfigData, MappingsCacheKey,
+    redaction::Redactor,
+    upload_backends::{upload, upload_backends, UploadBackend},
+    truncate,
+    util::format_bytes,
+    ConfigData, LogCacheKey, MappingsCacheKey,
 };
 
-#[derive(Deserialize, Clone)]
-struct UploadData {
-    url: Option<String>,
-    #[allow(dead_code)]
-    error: Option<String>,
-}
-
-#[derive(Serialize)]
-struct LogUpload<'a> {
-    content: &'a str,
-}
-
-type Log = (String, LogType, MapStatus, String, String);
+/// Name, how it was found, mapping status, link, the log itself, and how many personal details
+/// were redacted from it.
+type Log = (String, LogType, MapStatus, String, String, usize);
 
-pub(crate) enum LogType {
+#[derive(Clone)]
+pub enum LogType {
     Uploaded,
     Reuploaded,
     Downloaded,
 }
 
-pub(crate) enum MapStatus {
+#[derive(Clone)]
+pub enum MapStatus {
     InvalidMcVersion,
     Unmapped,
     Mapped(Duration),
@@ -68,49 +70,159 @@ pub(crate) async fn check_for_logs(
     compact: bool,
 ) -> Result<Option<(&'static str, Vec<CreateEmbed>, Vec<CreateActionRow>)>> {
     let mut data = ctx.data.write().await;
-    let attachments: Vec<_> =
-        if let Some(file_extensions) = &data.get::<ConfigData>().unwrap().log_extensions {
-            message
-                .attachments
-                .iter()
-                .filter(|attachment| all || is_valid_log(attachment, file_extensions))
-                .collect()
-        } else {
-            vec![]
-        };
-
-    let mappings_cache = data.get_mut::<MappingsCacheKey>().unwrap();
-
-    let mut logs: Vec<Log> = upload_log_files(mappings_cache, &attachments).await?;
-    logs.append(&mut check_pre_uploaded_logs(mappings_cache, &message.content).await?);
+    let logs = find_logs(&mut data, &message.attachments, &message.content, all).await?;
+    // Checking can wait on Modrinth for a while, commands that only read can run meanwhile
+    let data = data.downgrade();
 
     if logs.is_empty() {
         return Ok(None);
     }
 
-    let embeds = if compact {
-        vec![CreateEmbed::new()
-            .title(format!("Uploaded logs."))
-            .color(Severity::None.get_color())]
+    let (embeds, actions) = if compact {
+        (
+            vec![CreateEmbed::new()
+                .title(format!("Uploaded logs."))
+                .color(Severity::None.get_color())],
+            vec![],
+        )
     } else {
-        logs.iter()
-            .map(|(name, t, m, _, log)| check_logs(log, name, t, m))
-            .collect()
+        let log_cache = data.get::<LogCacheKey>().expect("No log cache?");
+        let mut embeds = vec![];
+        let mut actions: Vec<CheckAction> = vec![];
+        for (name, t, m, _, log, redacted) in &logs {
+            let start = Instant::now();
+            let (embed, log_actions) = match log_cache.report(log) {
+                Some((embed, actions)) => {
+                    (embed.title(t.title_format(name, &start.elapsed())), actions)
+                }
+                None => {
+                    let report = check_logs(log, name, t, m, &data).await;
+                    log_cache.insert_report(log, report.clone());
+                    report
+                }
+            };
+            embeds.push(with_redaction_note(embed, *redacted));
+            for action in log_actions {
+                if !actions.contains(&action) {
+                    actions.push(action);
+                }
+            }
+        }
+        (embeds, actions)
     };
 
-    let edit = (
-        "",
-        embeds,
-        vec![CreateActionRow::Buttons(
-            logs.iter()
-                .map(|(name, _, _, url, _)| CreateButton::new_link(url).label(name))
-                .collect(),
-        )],
+    let mut components = vec![CreateActionRow::Buttons(
+        logs.iter()
+            .map(|(name, _, _, url, _, _)| link_button(url, name))
+            .collect(),
+    )];
+    // Discord allows up to 5 rows of 5 buttons, the first row is taken by the log links
+    components.extend(
+        actions
+            .chunks(5)
+            .take(4)
+            .map(|row| {
+                CreateActionRow::Buttons(
+                    row.iter()
+                        .map(|action| link_button(&action.url, &action.label))
+                        .collect(),
+                )
+            }),
     );
 
+    let edit = ("", embeds, components);
+
     Ok(Some(edit))
 }
 
+/// Compares the first two logs found in the given attachments and messages, in order. Returns
+/// `None` when there are fewer than two logs.
+pub(crate) async fn check_for_log_diff(
+    ctx: &Context,
+    sources: &[(&[Attachment], &str)],
+) -> Result<Option<(CreateEmbed, Vec<CreateActionRow>)>> {
+    let mut data = ctx.data.write().await;
+    let mut logs = vec![];
+    for (attachments, content) in sources {
+        logs.append(&mut find_logs(&mut data, attachments, content, true).await?);
+    }
+    let data = data.downgrade();
+
+    let [
+        (old_name, _, old_map_status, old_url, old, old_redacted),
+        (new_name, _, new_map_status, new_url, new, new_redacted),
+        ..,
+    ] = logs.as_slice()
+    else {
+        return Ok(None);
+    };
+
+    let embed = diff_logs(old, old_map_status, new, new_map_status, &data)
+        .await
+        .title(format!("Changes from {old_name} to {new_name}"));
+    let embed = with_redaction_note(embed, old_redacted + new_redacted);
+    let components = vec![CreateActionRow::Buttons(vec![
+        link_button(old_url, old_name),
+        link_button(new_url, new_name),
+    ])];
+
+    Ok(Some((embed, components)))
+}
+
+fn link_button(url: &str, label: &str) -> CreateButton {
+    CreateButton::new_link(url).label(truncate!(label.to_string(), BUTTON_LABEL_LIMIT))
+}
+
+/// Lets people know their log was changed before being uploaded.
+fn with_redaction_note(embed: CreateEmbed, redacted: usize) -> CreateEmbed {
+    match redacted {
+        0 => embed,
+        1 => embed.footer(CreateEmbedFooter::new("Redacted 1 personal detail")),
+        n => embed.footer(CreateEmbedFooter::new(format!("Redacted {n} personal details"))),
+    }
+}
+
+/// Uploads the attached logs and downloads the linked ones.
+async fn find_logs(
+    data: &mut TypeMap,
+    attachments: &[Attachment],
+    content: &str,
+    all: bool,
+) -> Result<Vec<Log>> {
+    let config = data.get::<ConfigData>().unwrap();
+    let sources = log_sources(config);
+    let backends = upload_backends(config);
+    let redactor = Redactor::new(config);
+    // Cloned out, as the mappings cache is borrowed mutably alongside it
+    let log_cache = Arc::clone(data.get::<LogCacheKey>().expect("No log cache?"));
+    let attachments: Vec<_> =
+        if let Some(file_extensions) = &config.log_extensions {
+            attachments
+                .iter()
+                .filter(|attachment| all || is_valid_log(attachment, file_extensions))
+                .collect()
+        } else {
+            vec![]
+        };
+
+    let mappings_cache = data.get_mut::<MappingsCacheKey>().unwrap();
+
+    let mut logs: Vec<Log> =
+        upload_log_files(mappings_cache, &log_cache, &backends, &redactor, &attachments).await?;
+    logs.append(
+        &mut check_pre_uploaded_logs(
+            mappings_cache,
+            &log_cache,
+            &backends,
+            &redactor,
+            &sources,
+            content,
+        )
+        .await?,
+    );
+    Ok(logs)
+}
+
 fn is_valid_log<T: AsRef<str>>(attachment: &Attachment, allowed_extensions: &[T]) -> bool {
     attachment.size < 1_000_000
         && (allowed_extensions
@@ -142,6 +254,9 @@ async fn try_remap<'a>(
 
 async fn upload_log_files(
     mappings_cache: &mut MappingsCache,
+    log_cache: &LogCache,
+    backends: &[Box<dyn UploadBackend>],
+    redactor: &Redactor,
     attachments: &[&Attachment],
 ) -> Result<Vec<Log>> {
     let mut responses = vec![];
@@ -155,6 +270,25 @@ async fn upload_log_files(
             ));
         }
 
+        if let Some(kind) = ArchiveKind::from_file_name(&attachment.filename) {
+            let data = attachment.download().await?;
+            let archived = tokio::task::spawn_blocking(move || extract_logs(kind, &data)).await??;
+            for log in archived {
+                responses.push(
+                    upload_log_file(
+                        mappings_cache,
+                        log_cache,
+                        backends,
+                        redactor,
+                        log.file_name(),
+                        &log.data,
+                    )
+                    .await?,
+                );
+            }
+            continue;
+        }
+
         let data = if Path::new(&attachment.filename)
             .extension()
             .map_or(false, |ext| ext.eq_ignore_ascii_case("gz"))
@@ -172,185 +306,131 @@ async fn upload_log_files(
         } else {
             attachment.download().await?
         };
-        let log = String::from_utf8_lossy(&data);
+        responses.push(
+            upload_log_file(
+                mappings_cache,
+                log_cache,
+                backends,
+                redactor,
+                &attachment.filename,
+                &data,
+            )
+            .await?,
+        );
+    }
 
-        // Potentially perhaps remap some logs
-        let (log, map_status) = try_remap(mappings_cache, &log).await?;
+    Ok(responses)
+}
 
-        let data = upload(&log).await?;
+fn into_log(name: String, processed: ProcessedLog) -> Log {
+    (
+        name,
+        processed.t,
+        processed.map_status,
+        processed.url,
+        processed.log,
+        processed.redacted,
+    )
+}
 
-        if let Some(url) = data.url {
-            responses.push((
-                attachment.filename.clone(),
-                LogType::Uploaded,
-                map_status,
-                url,
-                log.into_owned(),
-            ));
-        } else {
-            return Err(anyhow!(
-                "Mclo.gs uploading error: {}",
-                data.error.unwrap_or("Unknown error".to_string())
-            ));
-        }
+async fn upload_log_file(
+    mappings_cache: &mut MappingsCache,
+    log_cache: &LogCache,
+    backends: &[Box<dyn UploadBackend>],
+    redactor: &Redactor,
+    name: &str,
+    data: &[u8],
+) -> Result<Log> {
+    let log = String::from_utf8_lossy(data);
+    if let Some(cached) = log_cache.log(&LogCache::key(&log, mappings_cache)) {
+        return Ok(into_log(name.to_string(), cached));
     }
 
-    Ok(responses)
+    // Potentially perhaps remap some logs
+    let (remapped, map_status) = try_remap(mappings_cache, &log).await?;
+    let (redacted_log, redacted) = redactor.redact(&remapped);
+
+    let url = upload(backends, &redacted_log).await?;
+
+    let processed = ProcessedLog {
+        t: LogType::Uploaded,
+        map_status,
+        url,
+        log: redacted_log.into_owned(),
+        redacted,
+    };
+    log_cache.insert_log(LogCache::key(&log, mappings_cache), processed.clone());
+    Ok(into_log(name.to_string(), processed))
 }
 
 async fn check_pre_uploaded_logs(
     mappings_cache: &mut MappingsCache,
+    log_cache: &LogCache,
+    backends: &[Box<dyn UploadBackend>],
+    redactor: &Redactor,
+    sources: &[Box<dyn LogSource>],
     message_content: &str,
 ) -> Result<Vec<Log>> {
-    let mut logs: Vec<_> = vec![];
-
-    for (url, id) in find_urls(r"https:\/\/mclo\.gs\/([a-zA-Z0-9]+)", message_content) {
-        let log_data = download(&id).await?;
-
-        logs.push((
-            id,
-            LogType::Downloaded,
-            MapStatus::NotRequired,
-            Some(url),
-            log_data,
-        ));
-    }
-
-    for (_, id) in find_urls(
-        r"https:\/\/paste\.gg\/p\/\w+\/([a-zA-Z0-9]+)",
-        message_content,
-    ) {
-        if let Some(log_data) = download_paste_gg(&id).await? {
-            logs.push((
-                id,
-                LogType::Downloaded,
-                MapStatus::NotRequired,
-                None,
-                log_data,
-            ));
-        }
-    }
-
-    for (_, id) in find_urls(r"https:\/\/pastebin\.com\/([a-zA-Z0-9]+)", message_content) {
-        let log_data = download_pastebin(&id).await?;
-
-        logs.push((
-            id,
-            LogType::Downloaded,
-            MapStatus::NotRequired,
-            None,
-            log_data,
-        ));
-    }
+    // Every link in the order it was posted in
+    let mut links = sources
+        .iter()
+        .flat_map(|source| {
+            source.pattern().captures_iter(message_content).map(move |caps| {
+                (
+                    caps.get(0).expect("Regex err"),
+                    caps.get(1).expect("Regex err").as_str(),
+                    source,
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+    links.sort_by_key(|(url, _, _)| url.start());
 
     let mut responses: Vec<Log> = vec![];
 
-    for log in logs {
-        let (remapped, map_status) = try_remap(mappings_cache, &log.4).await?;
+    for (url, id, source) in links {
+        let name = source.name(id).to_string();
+        if let Some(cached) = log_cache.linked_log(url.as_str()) {
+            responses.push(into_log(name, cached));
+            continue;
+        }
 
-        if *remapped == log.4
-            && let Some(url) = log.3
-        {
-            responses.push((log.0, log.1, MapStatus::NotRequired, url, log.4));
+        let Some(log) = source.fetch(id).await? else {
+            continue;
+        };
+        let key = LogCache::key(&log, mappings_cache);
+        if let Some(cached) = log_cache.log(&key) {
+            log_cache.insert_link(url.as_str(), key);
+            responses.push(into_log(name, cached));
+            continue;
+        }
+
+        let (remapped, map_status) = try_remap(mappings_cache, &log).await?;
+        let (redacted, redaction_count) = redactor.redact(&remapped);
+        let key = LogCache::key(&log, mappings_cache);
+
+        // Logs with personal details are reuploaded, so the bot doesn't link to them
+        let processed = if *redacted == log && !source.needs_reupload() {
+            ProcessedLog {
+                t: LogType::Downloaded,
+                map_status: MapStatus::NotRequired,
+                url: url.as_str().to_string(),
+                log,
+                redacted: 0,
+            }
         } else {
-            let data = upload(&remapped).await?;
-            responses.push((
-                log.0,
-                LogType::Reuploaded,
+            ProcessedLog {
+                t: LogType::Reuploaded,
                 map_status,
-                data.url.ok_or(anyhow!("Couldn't reupload"))?,
-                remapped.into_owned(),
-            ));
-        }
+                url: upload(backends, &redacted).await?,
+                log: redacted.into_owned(),
+                redacted: redaction_count,
+            }
+        };
+        log_cache.insert_log(key.clone(), processed.clone());
+        log_cache.insert_link(url.as_str(), key);
+        responses.push(into_log(name, processed));
     }
 
     Ok(responses)
 }
-
-fn find_urls(regex: &str, message_content: &str) -> Vec<(String, String)> {
-    let regex = Regex::new(regex).unwrap();
-
-    regex
-        .captures_iter(message_content)
-        .map(|caps| {
-            (
-                caps.get(0).expect("Regex err").as_str().to_string(),
-                caps.get(1).expect("Regex err").as_str().to_string(),
-            )
-        })
-        .collect()
-}
-
-async fn upload(log: &str) -> Result<UploadData> {
-    let client = create_http()?;
-
-    Ok(client
-        .post(format!("{MCLOGS_API_BASE_URL}/1/log"))
-        .header("Content-Type", "application/x-www-form-urlencoded")
-        .body(serde_urlencoded::to_string(LogUpload { content: log })?)
-        .send()
-        .await?
-        .json::<UploadData>()
-        .await?)
-}
-
-async fn download(id: &str) -> Result<String> {
-    let client = create_http()?;
-
-    Ok(client
-        .get(format!("{MCLOGS_API_BASE_URL}/1/raw/{id}"))
-        .send()
-        .await?
-        .text()
-        .await?)
-}
-
-#[derive(Deserialize)]
-struct GGResponse {
-    result: GGResult,
-}
-
-#[derive(Deserialize)]
-struct GGResult {
-    files: Vec<GGFile>,
-}
-
-#[derive(Deserialize)]
-struct GGFile {
-    content: GGContent,
-}
-
-#[derive(Deserialize)]
-struct GGContent {
-    value: String,
-}
-
-async fn download_paste_gg(id: &str) -> Result<Option<String>> {
-    let client = create_http()?;
-
-    let mut response = client
-        .get(format!("{PASTE_GG_API_BASE_URL}/pastes/{id}?full=true"))
-        .send()
-        .await?
-        .json::<GGResponse>()
-        .await?;
-
-    if response.result.files.len() == 0 {
-        return Ok(None);
-    }
-
-    Ok(Some(response.result.files.remove(0).content.value))
-}
-
-async fn download_pastebin(id: &str) -> Result<String> {
-    let client = create_http()?;
-
-    let response = client
-        .get(format!("{PASTEBIN_URL}/raw/{id}"))
-        .send()
-        .await?
-        .text()
-        .await?;
-
-    Ok(response)
-}
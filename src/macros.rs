@@ -0,0 +1,9 @@
+/// Compiles the given regex once, on first use, and returns a `&'static Regex` to it.
+#[macro_export]
+macro_rules! static_regex {
+    ($regex:expr) => {{
+        static REGEX: std::sync::LazyLock<regex::Regex> =
+            std::sync::LazyLock::new(|| regex::Regex::new($regex).expect("Incorrect regex"));
+        &*REGEX
+    }};
+}
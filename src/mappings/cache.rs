@@ -5,6 +5,7 @@ use anyhow::Result;
 use std::collections::HashMap;
 
 struct CacheEntry {
+    yarn_version: String,
     mappings: Mappings,
     hits: u32,
 }
@@ -25,15 +26,8 @@ impl MappingsCache {
         mc_version: &str,
     ) -> Result<Option<&'a Mappings>> {
         if !self.cache.contains_key(mc_version) {
-            if let Some(downloaded) = download_mappings(mc_version).await? {
-                self.try_invalidate();
-                self.cache.insert(
-                    mc_version.to_string(),
-                    CacheEntry {
-                        mappings: downloaded,
-                        hits: 0,
-                    },
-                );
+            if let Some((yarn_version, downloaded)) = download_mappings(mc_version).await? {
+                self.insert(mc_version, yarn_version, downloaded);
             }
         }
 
@@ -43,6 +37,19 @@ impl MappingsCache {
         }))
     }
 
+    /// Stores the mappings for a version, making room for them first.
+    pub fn insert(&mut self, mc_version: &str, yarn_version: String, mappings: Mappings) {
+        self.try_invalidate();
+        self.cache.insert(
+            mc_version.to_string(),
+            CacheEntry {
+                yarn_version,
+                mappings,
+                hits: 0,
+            },
+        );
+    }
+
     pub fn try_invalidate(&mut self) {
         if self.cache.len() > MAPPINGS_CACHE_LIMIT {
             let remove = {
@@ -60,6 +67,11 @@ impl MappingsCache {
         }
     }
 
+    /// The yarn build cached for the version, without downloading it.
+    pub fn yarn_version(&self, mc_version: &str) -> Option<&str> {
+        self.cache.get(mc_version).map(|c| c.yarn_version.as_str())
+    }
+
     pub fn cached_keys<'a>(&'a self) -> Vec<&'a String> {
         self.cache.keys().collect()
     }
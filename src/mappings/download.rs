@@ -9,7 +9,8 @@ use crate::{commands::version::get_yarn_version, util::create_http};
 
 use super::Mappings;
 
-pub async fn download_mappings(mc_version: &str) -> Result<Option<Mappings>> {
+/// Downloads the latest yarn build for the version, returning its version along with it.
+pub async fn download_mappings(mc_version: &str) -> Result<Option<(String, Mappings)>> {
     if let Some(yarn_version) = get_yarn_version(mc_version).await?.version {
         let yarn_jar = &create_http()?
             .get(format!(
@@ -27,7 +28,7 @@ pub async fn download_mappings(mc_version: &str) -> Result<Option<Mappings>> {
 
         let tiny_file = zip.by_name("mappings/mappings.tiny")?;
 
-        Ok(Some(parse_mappings(tiny_file)?))
+        Ok(Some((yarn_version, parse_mappings(tiny_file)?)))
     } else {
         Ok(None)
     }
@@ -3,7 +3,7 @@ pub mod download;
 
 use std::collections::HashMap;
 
-use regex::{Captures, Regex};
+use regex::Captures;
 
 pub struct Mappings {
     // `class_23232` -> `net.minecraft.something.Something`
@@ -18,8 +18,7 @@ pub struct Mappings {
 
 macro_rules! replace_part {
     ($hay:expr,$map:expr,$regex:expr,$mapper:expr) => {{
-        let regex = Regex::new($regex).expect("regex");
-        regex.replace_all($hay, |caps: &Captures| {
+        $crate::static_regex!($regex).replace_all($hay, |caps: &Captures| {
             $map.get(caps.get(1).expect("regex err").as_str())
                 .map($mapper)
                 .unwrap_or_else(|| caps.get(0).expect("regex err").as_str().to_string())
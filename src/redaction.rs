@@ -0,0 +1,211 @@
+use std::{borrow::Cow, sync::LazyLock};
+
+use regex::{Captures, Regex};
+
+use crate::config::Config;
+
+/// Personal details and secrets that are taken out of logs before they're uploaded.
+struct RedactionRule {
+    /// Name used to turn the rule off in the config.
+    name: &'static str,
+    /// Text around the redacted part that should stay is captured as `before` and `after`.
+    patterns: &'static [&'static str],
+    replacement: &'static str,
+}
+
+const RULES: &[RedactionRule] = &[
+    RedactionRule {
+        name: "user_paths",
+        patterns: &[
+            // Skipping what's already been redacted, so logs that are posted again are left as
+            // they are, and forward slash Windows paths aren't redacted again as macOS ones
+            r"(?i)(?P<before>[A-Z]:[\\/]+Users[\\/]+)[^<\\/\r\n][^\\/\r\n]*",
+            r"(?P<before>/home/)[^</\s][^/\s]*",
+            r"(?P<before>/Users/)[^</\s][^/\s]*",
+        ],
+        replacement: "<user>",
+    },
+    RedactionRule {
+        name: "access_tokens",
+        patterns: &[
+            r"(?P<before>--(?:accessToken|session),? )[^<\s,\]][^\s,\]]*",
+            r"(?i)(?P<before>session(?:_?id)?[=:] ?)[\w.-]{16,}",
+            r"(?P<before>token:)[0-9a-f]{32}",
+        ],
+        replacement: "<token>",
+    },
+    RedactionRule {
+        name: "ip_addresses",
+        // Only where an address is expected, as versions can look the same, like in the paths
+        // to libraries
+        patterns: &[
+            r"(?m)(?P<before>/|Connecting to |connect to )(?:\d{1,3}\.){3}\d{1,3}(?P<after>$|[^\w./:-])",
+            r"\b(?:\d{1,3}\.){3}\d{1,3}(?P<after>:\d{2,5}\b)",
+        ],
+        replacement: "<ip>",
+    },
+    RedactionRule {
+        name: "emails",
+        patterns: &[r"\b[\w.+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,24}\b"],
+        replacement: "<email>",
+    },
+];
+
+static REGEXES: LazyLock<Vec<Vec<Regex>>> = LazyLock::new(|| {
+    RULES
+        .iter()
+        .map(|rule| {
+            rule.patterns
+                .iter()
+                .map(|p| Regex::new(p).expect("Incorrect regex"))
+                .collect()
+        })
+        .collect()
+});
+
+/// The redaction rules enabled in the config.
+pub(crate) struct Redactor {
+    /// Indices into [`RULES`].
+    rules: Vec<usize>,
+}
+
+impl Redactor {
+    /// Every rule is enabled unless turned off in the config's `redaction` table.
+    pub(crate) fn new(config: &Config) -> Redactor {
+        let rules = RULES
+            .iter()
+            .enumerate()
+            .filter(|(_, rule)| {
+                config
+                    .redaction
+                    .as_ref()
+                    .and_then(|r| r.get(rule.name))
+                    .copied()
+                    .unwrap_or(true)
+            })
+            .map(|(i, _)| i)
+            .collect();
+        Redactor { rules }
+    }
+
+    /// Returns the log with personal details replaced, along with how many were replaced.
+    pub(crate) fn redact<'a>(&self, log: &'a str) -> (Cow<'a, str>, usize) {
+        let mut redacted = Cow::Borrowed(log);
+        let mut count = 0;
+        for &i in &self.rules {
+            let replacement = RULES[i].replacement;
+            for regex in &REGEXES[i] {
+                if !regex.is_match(&redacted) {
+                    continue;
+                }
+                let replaced = regex
+                    .replace_all(&redacted, |caps: &Captures| {
+                        count += 1;
+                        let kept = |name| caps.name(name).map_or("", |m| m.as_str());
+                        format!("{}{replacement}{}", kept("before"), kept("after"))
+                    })
+                    .into_owned();
+                redacted = Cow::Owned(replaced);
+            }
+        }
+        (redacted, count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Redactor, RULES};
+
+    fn redact(log: &str) -> (String, usize) {
+        let redactor = Redactor {
+            rules: (0..RULES.len()).collect(),
+        };
+        let (redacted, count) = redactor.redact(log);
+        // Logs are redacted again when they're posted again
+        assert_eq!(redactor.redact(&redacted), (redacted.clone(), 0));
+        (redacted.into_owned(), count)
+    }
+
+    #[test]
+    fn user_paths() {
+        assert_eq!(
+            redact(r"Game directory: C:\Users\John Smith\AppData\Roaming\.minecraft"),
+            (
+                r"Game directory: C:\Users\<user>\AppData\Roaming\.minecraft".to_string(),
+                1
+            )
+        );
+        assert_eq!(
+            redact("Loading C:/Users/John Smith/curseforge/minecraft/Instances/Pack/mods"),
+            (
+                "Loading C:/Users/<user>/curseforge/minecraft/Instances/Pack/mods".to_string(),
+                1
+            )
+        );
+        assert_eq!(
+            redact("at /home/john/.local/share/PrismLauncher and /Users/jane/Library"),
+            (
+                "at /home/<user>/.local/share/PrismLauncher and /Users/<user>/Library".to_string(),
+                2
+            )
+        );
+    }
+
+    #[test]
+    fn access_tokens() {
+        assert_eq!(
+            redact(
+                "[--username, Player, --accessToken, eyJhbGciOiJIUzI1NiJ9.e30.sig, --version, 1.20.1]"
+            ),
+            (
+                "[--username, Player, --accessToken, <token>, --version, 1.20.1]".to_string(),
+                1
+            )
+        );
+        assert_eq!(
+            redact("--session 0123456789abcdef --uuid x"),
+            ("--session <token> --uuid x".to_string(), 1)
+        );
+        assert_eq!(
+            redact("Session ID is token:0123456789abcdef0123456789abcdef:c06f89064c8a4916"),
+            (
+                "Session ID is token:<token>:c06f89064c8a4916".to_string(),
+                1
+            )
+        );
+        assert_eq!(
+            redact("session_id=AbCdEfGhIjKlMnOpQrSt"),
+            ("session_id=<token>".to_string(), 1)
+        );
+    }
+
+    #[test]
+    fn ip_addresses() {
+        assert_eq!(
+            redact("Connecting to 203.0.113.7, 25565\n/198.51.100.2:51234 lost connection"),
+            (
+                "Connecting to <ip>, 25565\n/<ip>:51234 lost connection".to_string(),
+                2
+            )
+        );
+        assert_eq!(
+            redact("Disconnected from localhost/127.0.0.1"),
+            ("Disconnected from localhost/<ip>".to_string(), 1)
+        );
+        // Versions that look like addresses
+        let log = "libraries/org/lwjgl/lwjgl/3.3.1.2/lwjgl-3.3.1.2.jar, minecraft/1.20.1.2/";
+        assert_eq!(redact(log), (log.to_string(), 0));
+    }
+
+    #[test]
+    fn emails() {
+        assert_eq!(
+            redact("Logged in as player.one+mc@example.co.uk"),
+            ("Logged in as <email>".to_string(), 1)
+        );
+        // Java's default toString, which has an @ too
+        let log =
+            "net.minecraft.class_310@1a2b3c4d, Foo@1a2b and [Lnet.minecraft.class_1799;@6f7e8d9c";
+        assert_eq!(redact(log), (log.to_string(), 0));
+    }
+}
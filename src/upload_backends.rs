@@ -0,0 +1,236 @@
+use std::{io, path::PathBuf};
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use serenity::async_trait;
+use sha2::{Digest, Sha256};
+use tokio::{fs, io::AsyncWriteExt};
+
+use crate::{
+    config::{Config, UploadBackendConfig},
+    constants::{MCLOGS_API_BASE_URL, PASTE_GG_API_BASE_URL, PASTE_GG_URL, UPLOAD_TIMEOUT},
+    util::create_http,
+};
+
+/// A place logs are uploaded to, so they can be linked to and read in a browser.
+#[async_trait]
+pub(crate) trait UploadBackend: Send + Sync {
+    /// Name used when reporting errors.
+    fn name(&self) -> &'static str;
+
+    /// Uploads the log, returning the link to view it at.
+    async fn upload(&self, log: &str) -> Result<String>;
+}
+
+/// The backends from the config, in the order they should be tried. Just mclo.gs if none are
+/// configured.
+pub(crate) fn upload_backends(config: &Config) -> Vec<Box<dyn UploadBackend>> {
+    let Some(backends) = &config.upload_backends else {
+        return vec![Box::new(McLogs {
+            api_url: MCLOGS_API_BASE_URL.to_string(),
+        })];
+    };
+    backends
+        .iter()
+        .map(|backend| -> Box<dyn UploadBackend> {
+            match backend {
+                UploadBackendConfig::Mclogs { api_url } => Box::new(McLogs {
+                    api_url: api_url.as_deref().unwrap_or(MCLOGS_API_BASE_URL).to_string(),
+                }),
+                UploadBackendConfig::PasteGg { api_key } => Box::new(PasteGg {
+                    api_key: api_key.clone(),
+                }),
+                UploadBackendConfig::Local { directory, url } => Box::new(Local {
+                    directory: PathBuf::from(directory),
+                    url: url.trim_end_matches('/').to_string(),
+                }),
+            }
+        })
+        .collect()
+}
+
+/// Uploads to each backend in turn until one succeeds.
+pub(crate) async fn upload(backends: &[Box<dyn UploadBackend>], log: &str) -> Result<String> {
+    let mut errors = vec![];
+    for backend in backends {
+        match backend.upload(log).await {
+            Ok(url) => return Ok(url),
+            Err(err) => {
+                println!("Failed to upload log to {}: {err}", backend.name());
+                errors.push(format!("{}: {err}", backend.name()));
+            }
+        }
+    }
+    Err(anyhow!("Uploading failed, {}", errors.join(", ")))
+}
+
+#[derive(Deserialize)]
+struct UploadData {
+    url: Option<String>,
+    error: Option<String>,
+}
+
+#[derive(Serialize)]
+struct LogUpload<'a> {
+    content: &'a str,
+}
+
+/// mclo.gs, or a self-hosted instance of it.
+struct McLogs {
+    api_url: String,
+}
+
+#[async_trait]
+impl UploadBackend for McLogs {
+    fn name(&self) -> &'static str {
+        "mclo.gs"
+    }
+
+    async fn upload(&self, log: &str) -> Result<String> {
+        let data = create_http()?
+            .post(format!("{}/1/log", self.api_url))
+            .timeout(UPLOAD_TIMEOUT)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(serde_urlencoded::to_string(LogUpload { content: log })?)
+            .send()
+            .await?
+            .json::<UploadData>()
+            .await?;
+
+        data.url
+            .ok_or_else(|| anyhow!("{}", data.error.unwrap_or("Unknown error".to_string())))
+    }
+}
+
+#[derive(Deserialize)]
+struct GGCreated {
+    result: Option<GGPaste>,
+    message: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GGPaste {
+    id: String,
+    /// Only set for pastes made with a key.
+    author: Option<GGAuthor>,
+}
+
+#[derive(Deserialize)]
+struct GGAuthor {
+    username: String,
+}
+
+struct PasteGg {
+    /// Pastes are anonymous without a key.
+    api_key: Option<String>,
+}
+
+#[async_trait]
+impl UploadBackend for PasteGg {
+    fn name(&self) -> &'static str {
+        "paste.gg"
+    }
+
+    async fn upload(&self, log: &str) -> Result<String> {
+        let mut request = create_http()?
+            .post(format!("{PASTE_GG_API_BASE_URL}/pastes"))
+            .timeout(UPLOAD_TIMEOUT)
+            .json(&json!({
+                "visibility": "unlisted",
+                "files": [{
+                    "name": "log.txt",
+                    "content": { "format": "text", "value": log },
+                }],
+            }));
+        if let Some(key) = &self.api_key {
+            request = request.header("Authorization", format!("Key {key}"));
+        }
+
+        let created = request.send().await?.json::<GGCreated>().await?;
+        match created.result {
+            Some(paste) => {
+                let user = paste.author.map_or("anonymous".to_string(), |a| a.username);
+                Ok(format!("{PASTE_GG_URL}/p/{user}/{}", paste.id))
+            }
+            None => Err(anyhow!(
+                "{}",
+                created.message.unwrap_or("Unknown error".to_string())
+            )),
+        }
+    }
+}
+
+/// Writes logs to a directory served by a web server.
+struct Local {
+    directory: PathBuf,
+    /// Where the directory can be reached, without a trailing slash.
+    url: String,
+}
+
+#[async_trait]
+impl UploadBackend for Local {
+    fn name(&self) -> &'static str {
+        "local"
+    }
+
+    async fn upload(&self, log: &str) -> Result<String> {
+        // Named after the contents, so uploading the same log twice doesn't store it twice
+        let file_name = format!("{:x}.txt", Sha256::digest(log));
+
+        fs::create_dir_all(&self.directory).await?;
+        let path = self.directory.join(&file_name);
+        let mut file = match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .await
+        {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {
+                return Ok(format!("{}/{file_name}", self.url));
+            }
+            Err(err) => return Err(err.into()),
+        };
+        // A partly written file would be linked to every time the log is uploaded again
+        if let Err(err) = write_all(&mut file, log).await {
+            let _ = fs::remove_file(&path).await;
+            return Err(err.into());
+        }
+        Ok(format!("{}/{file_name}", self.url))
+    }
+}
+
+async fn write_all(file: &mut fs::File, log: &str) -> io::Result<()> {
+    file.write_all(log.as_bytes()).await?;
+    file.flush().await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn local_upload() {
+        let directory = env::temp_dir().join(format!("mib-uploads-{}", process::id()));
+        let local = Local {
+            directory: directory.clone(),
+            url: "https://logs.example.com".to_string(),
+        };
+
+        let url = local.upload("[main/INFO]: Hello").await.unwrap();
+        let name = url.strip_prefix("https://logs.example.com/").unwrap();
+        assert_eq!(name.len(), 64 + ".txt".len());
+        assert_eq!(
+            fs::read_to_string(directory.join(name)).await.unwrap(),
+            "[main/INFO]: Hello"
+        );
+        // The same log isn't written again, and a different one doesn't replace it
+        assert_eq!(local.upload("[main/INFO]: Hello").await.unwrap(), url);
+        assert_ne!(local.upload("[main/INFO]: Bye").await.unwrap(), url);
+
+        fs::remove_dir_all(directory).await.unwrap();
+    }
+}
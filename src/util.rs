@@ -1,6 +1,8 @@
 use number_prefix::NumberPrefix;
 use reqwest::Client;
 
+use crate::constants::HTTP_CONNECT_TIMEOUT;
+
 pub fn format_bytes(bytes: u32) -> String {
     match NumberPrefix::decimal(bytes as f32) {
         NumberPrefix::Standalone(amount) => {
@@ -15,5 +17,6 @@ pub fn format_bytes(bytes: u32) -> String {
 pub fn create_http() -> reqwest::Result<reqwest::Client> {
     Client::builder()
         .user_agent("enjarai/mental-instability-bot (enjarai@protonmail.com)")
+        .connect_timeout(HTTP_CONNECT_TIMEOUT)
         .build()
 }
\ No newline at end of file
@@ -0,0 +1,139 @@
+//! Runs the full check pipeline over every log in `tests/fixtures/logs` and compares the reports
+//! with the `<name>.expected.toml` file next to it.
+//!
+//! Run with `UPDATE_GOLDEN=1` to rewrite the expectations after an intentional change.
+
+use std::{
+    env,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use mental_instability_bot::{
+    log_checking::{
+        checks::{check_checks, Severity},
+        declarative::load_declarative_checks,
+        environment::get_environment_info,
+        incompatibilities::load_incompatibilities,
+        minecraft::VersionManifest,
+    },
+    log_upload::MapStatus,
+};
+use serde::Deserialize;
+
+const FIXTURES: &str = "tests/fixtures/logs";
+const MANIFEST: &str = "tests/fixtures/version_manifest.json";
+
+#[derive(Deserialize)]
+struct Expected {
+    /// The name of the launcher the log was detected to come from.
+    launcher: Option<String>,
+    /// Every discovered mod as `id version`, prefixed by `parent/` if it's bundled.
+    #[serde(default)]
+    mods: Vec<String>,
+    #[serde(default)]
+    checks: Vec<ExpectedCheck>,
+}
+
+#[derive(Deserialize, PartialEq, Debug)]
+struct ExpectedCheck {
+    id: String,
+    title: String,
+    severity: Severity,
+}
+
+fn fixtures() -> Vec<PathBuf> {
+    let mut paths = fs::read_dir(FIXTURES)
+        .expect("reading fixtures directory")
+        .map(|file| file.expect("locating fixture").path())
+        .filter(|path| {
+            path.extension()
+                .map_or(false, |ext| ext == "log" || ext == "txt")
+        })
+        .collect::<Vec<_>>();
+    paths.sort();
+    paths
+}
+
+fn to_toml(launcher: Option<&str>, mods: &[String], checks: &[ExpectedCheck]) -> String {
+    let mut output = String::new();
+    if let Some(launcher) = launcher {
+        let _ = writeln!(output, "launcher = {launcher:?}");
+    }
+    output.push_str("mods = [\n");
+    for discovered_mod in mods {
+        let _ = writeln!(output, "    {discovered_mod:?},");
+    }
+    output.push_str("]\nchecks = [\n");
+    for check in checks {
+        let _ = writeln!(
+            output,
+            "    {{ id = {:?}, title = {:?}, severity = \"{:?}\" }},",
+            check.id, check.title, check.severity
+        );
+    }
+    output.push_str("]\n");
+    output
+}
+
+#[test]
+fn golden_logs() {
+    let update = env::var_os("UPDATE_GOLDEN").is_some();
+    let declarative_checks = load_declarative_checks();
+    let incompatibilities = load_incompatibilities().expect("loading incompatibilities");
+    // A small manifest made for the tests, so the expectations don't change with every release
+    // of the game
+    let manifest = VersionManifest::read(Path::new(MANIFEST)).expect("loading version manifest");
+    let fixtures = fixtures();
+    assert!(!fixtures.is_empty(), "no fixtures found in {FIXTURES}");
+
+    let mut failures = vec![];
+    for path in fixtures {
+        let log = String::from_utf8_lossy(&fs::read(&path).expect("reading fixture")).into_owned();
+        let mut ctx = get_environment_info(&log, &MapStatus::NotRequired);
+        ctx.game_version = ctx.mc_version.as_deref().and_then(|v| manifest.game_version(v));
+        let mut mods = ctx
+            .discovered_mods
+            .iter()
+            .map(|m| match m.parent {
+                Some(parent) => format!("{parent}/{} {}", m.id, m.version),
+                None => format!("{} {}", m.id, m.version),
+            })
+            .collect::<Vec<_>>();
+        mods.sort();
+        let launcher = ctx.launcher.as_ref().map(|launcher| launcher.name());
+        let actual = check_checks(&log, &ctx, &declarative_checks, &incompatibilities)
+            .into_iter()
+            .map(|report| ExpectedCheck {
+                id: report.id,
+                title: report.title,
+                severity: report.severity,
+            })
+            .collect::<Vec<_>>();
+
+        let expected_path = path.with_extension("expected.toml");
+        if update {
+            fs::write(&expected_path, to_toml(launcher, &mods, &actual)).expect("writing expectations");
+            continue;
+        }
+
+        let expected = fs::read_to_string(&expected_path)
+            .map(|s| toml::from_str::<Expected>(&s).expect("parsing expectations"))
+            .unwrap_or_else(|_| panic!("missing {}", expected_path.display()));
+
+        if launcher != expected.launcher.as_deref()
+            || mods != expected.mods
+            || actual != expected.checks
+        {
+            failures.push(format!(
+                "{}:\nexpected:\n{}actual:\n{}",
+                path.display(),
+                to_toml(expected.launcher.as_deref(), &expected.mods, &expected.checks),
+                to_toml(launcher, &mods, &actual)
+            ));
+        }
+    }
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}